@@ -0,0 +1,20 @@
+use advent_of_code::template::bench::Bench;
+
+// the solution is compiled as a module of this bench, leaving its `main` and tests unused.
+#[allow(dead_code, unused_imports)]
+mod solution {
+    pub fn register(bench: &mut advent_of_code::template::bench::Bench, input: &str) {
+        bench.function("parse_input", || parse_input(input));
+        bench.function("part_one", || part_one(input));
+        bench.function("part_two", || part_two(input));
+    }
+
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/05.rs"));
+}
+
+fn main() {
+    let mut bench = Bench::from_env(advent_of_code::day!(5));
+    let input = advent_of_code::template::read_file("inputs", bench.day());
+    solution::register(&mut bench, &input);
+    bench.finish();
+}
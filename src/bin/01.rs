@@ -1,4 +1,6 @@
-advent_of_code::solution!(1);
+use advent_of_code::template::generator::Rng;
+
+advent_of_code::solution!(1, generator = generate_input, base_size = 4000);
 
 const DIAL_SIZE: i64 = 100;
 const DIAL_INITIAL: i64 = 50;
@@ -69,6 +71,16 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(count as u64)
 }
 
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = if rng.chance(1, 2) { 'L' } else { 'R' };
+            format!("{direction}{}", rng.range(1..1000))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,15 @@
 use std::ops::RangeInclusive;
 
+use advent_of_code::template::generator::Rng;
 use hashbrown::HashSet;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(
+    2,
+    generator = generate_input,
+    base_size = 40,
+    reference_one = reference_part_one,
+    reference_two = reference_part_two,
+);
 
 fn parse_input(input: &str) -> Vec<RangeInclusive<u64>> {
     input
@@ -119,6 +126,64 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(total)
 }
 
+// Brute force references for `cargo crosscheck`
+
+fn is_repeated(id: &str, times: usize) -> bool {
+    id.len().is_multiple_of(times) && id == id[..id.len() / times].repeat(times)
+}
+
+fn reference_part_one(input: &str) -> Option<u64> {
+    let total = parse_input(input)
+        .into_iter()
+        .flatten()
+        .filter(|id| is_repeated(&id.to_string(), 2))
+        .sum();
+
+    Some(total)
+}
+
+fn reference_part_two(input: &str) -> Option<u64> {
+    let total = parse_input(input)
+        .into_iter()
+        .flatten()
+        .filter(|id| {
+            let id = id.to_string();
+            (2..=id.len()).any(|times| is_repeated(&id, times))
+        })
+        .sum();
+
+    Some(total)
+}
+
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut ranges: Vec<(u64, u64)> = (0..size)
+        .map(|_| {
+            let digits = rng.range(1..11) as u32;
+            let start = if rng.chance(1, 4) {
+                // Straddle a digit count boundary
+                (10u64.pow(digits) - rng.range(0..500)).max(1)
+            } else {
+                rng.range(10u64.pow(digits - 1)..10u64.pow(digits))
+            };
+
+            (start, start + rng.range(0..1000))
+        })
+        .collect();
+
+    // Ranges in real inputs never overlap
+    ranges.sort_unstable();
+    for i in 1..ranges.len() {
+        ranges[i].0 = ranges[i].0.max(ranges[i - 1].1 + 1);
+        ranges[i].1 = ranges[i].1.max(ranges[i].0);
+    }
+
+    ranges
+        .iter()
+        .map(|(start, end)| format!("{start}-{end}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,14 @@
-advent_of_code::solution!(3);
+use advent_of_code::template::generator::Rng;
+
+advent_of_code::solution!(
+    3,
+    generator = generate_input,
+    base_size = 200,
+    reference_one = reference_part_one,
+    reference_two = reference_part_two,
+    variant_one = ("generic", reference_part_one),
+    variant_two = ("dynamic", reference_part_two),
+);
 
 fn parse_input(input: &str) -> Vec<Vec<u8>> {
     input
@@ -69,6 +79,52 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(result)
 }
 
+// References for `cargo crosscheck`, also compared as variants by `cargo solve 3 --variants`
+
+fn reference_part_one(input: &str) -> Option<u64> {
+    let banks = parse_input(input);
+    let result = banks.iter().map(|bank| calculate_joltage::<2>(bank)).sum();
+
+    Some(result)
+}
+
+fn reference_part_two(input: &str) -> Option<u64> {
+    // best[k][i] is the largest k-digit joltage using batteries from i onwards
+    let banks = parse_input(input);
+    let result = banks
+        .iter()
+        .map(|bank| {
+            let n = bank.len();
+            let mut best = vec![vec![None; n + 1]; 13];
+            best[0] = vec![Some(0u64); n + 1];
+
+            for k in 1..=12 {
+                for i in (0..n).rev() {
+                    let take = best[k - 1][i + 1]
+                        .map(|rest| bank[i] as u64 * 10u64.pow(k as u32 - 1) + rest);
+                    best[k][i] = take.max(best[k][i + 1]);
+                }
+            }
+
+            best[12][0].unwrap()
+        })
+        .sum();
+
+    Some(result)
+}
+
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let len = 12 + rng.index(size.min(100) + 1);
+            (0..len)
+                .map(|_| char::from(b'0' + rng.range(1..10) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
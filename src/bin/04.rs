@@ -1,37 +1,32 @@
 use advent_of_code::utils::{
+    automaton::Automaton,
+    bit_grid::{BitGrid, Words},
     grid::Grid,
-    point::{DIAGONAL, Point},
 };
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(
+    4,
+    variant_one = ("bits", part_one_bits),
+    variant_two = ("bits", part_two_bits),
+);
 
 fn parse_input(input: &str) -> Grid<u8> {
     Grid::parse(input)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let grid = parse_input(input);
+    // Empty border, so that neighbors of edge rolls need no bounds checks
+    let grid = parse_input(input).padded(1, b'.');
     let mut total = 0;
 
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            let point = Point::new(x, y);
-
-            if grid[point] != b'@' {
-                continue;
-            }
-
-            let count = DIAGONAL
-                .iter()
-                .filter(|d| {
-                    let n = point + **d;
-                    n.x >= 0 && n.y >= 0 && n.x < grid.width && n.y < grid.height && grid[n] == b'@'
-                })
-                .count();
-
-            if count < 4 {
-                total += 1;
-            }
+    for point in grid.find_all(&b'@') {
+        let count = grid
+            .neighbor_cells(point)
+            .filter(|(_, cell)| **cell == b'@')
+            .count();
+
+        if count < 4 {
+            total += 1;
         }
     }
 
@@ -39,55 +34,44 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let grid = parse_input(input);
-    // New grid that holds the number of neighboring rolls
-    // for each roll cell
-    let mut count_grid = Grid::new(grid.width, grid.height, i32::MAX);
-    let mut todo = vec![];
-
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            let point = Point::new(x, y);
-
-            if grid[point] != b'@' {
-                continue;
-            }
-
-            let count = DIAGONAL
-                .iter()
-                .filter(|d| {
-                    let n = point + **d;
-                    n.x >= 0 && n.y >= 0 && n.x < grid.width && n.y < grid.height && grid[n] == b'@'
-                })
-                .count();
-
-            count_grid[point] = count as i32;
-
-            if count < 4 {
-                todo.push(point);
-            }
-        }
-    }
+    // Remove accessible rolls until none are left, every change is a removed roll
+    let mut automaton = Automaton::new(
+        parse_input(input),
+        |cell| *cell == b'@',
+        |cell, rolls| match cell {
+            b'@' if rolls < 4 => b'.',
+            _ => *cell,
+        },
+    );
+
+    Some(automaton.settle() as u64)
+}
+
+// Variants for `cargo solve 4 --variants`
+
+fn accessible(rolls: &BitGrid) -> BitGrid {
+    rolls & &rolls.neighbor_counts().less_than(4)
+}
+
+fn part_one_bits(input: &str) -> Option<u64> {
+    let rolls = BitGrid::from_grid(&parse_input(input), |c| *c == b'@');
+    Some(accessible(&rolls).count_ones() as u64)
+}
 
+fn part_two_bits(input: &str) -> Option<u64> {
+    // Removing rolls only lowers neighbor counts, so removing all accessible rolls at once
+    // ends up with the same rolls as removing them one by one.
+    let mut rolls = BitGrid::from_grid(&parse_input(input), |c| *c == b'@');
     let mut total = 0;
 
-    // Start deleting rolls
-    while let Some(point) = todo.pop() {
-        total += 1;
-
-        for neighbor in DIAGONAL
-            .into_iter()
-            .map(|d| point + d)
-            .filter(|n| n.x >= 0 && n.y >= 0 && n.x < grid.width && n.y < grid.height)
-        {
-            // If a roll causes a neighbor roll to become
-            // accessible, add that to todo
-            if count_grid[neighbor] == 4 {
-                todo.push(neighbor);
-            }
-
-            count_grid[neighbor] -= 1;
+    loop {
+        let removed = accessible(&rolls);
+        if removed.is_empty() {
+            break;
         }
+
+        total += removed.count_ones() as u64;
+        rolls = rolls.and_not(&removed);
     }
 
     Some(total)
@@ -108,4 +92,16 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(43));
     }
+
+    #[test]
+    fn test_part_one_bits() {
+        let result = part_one_bits(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(13));
+    }
+
+    #[test]
+    fn test_part_two_bits() {
+        let result = part_two_bits(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(43));
+    }
 }
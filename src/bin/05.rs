@@ -1,9 +1,10 @@
 use std::ops::RangeInclusive;
 
+use advent_of_code::template::generator::Rng;
 use advent_of_code::utils::range::{Range, union_vec};
 use range_set::RangeSet;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, generator = generate_input, base_size = 200);
 
 fn parse_input(input: &str) -> (Vec<RangeInclusive<u64>>, Vec<u64>) {
     let (fresh_ranges, inventory) = input.split_once("\n\n").unwrap();
@@ -56,6 +57,23 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(result)
 }
 
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    const MAX_ID: u64 = 500_000_000_000_000;
+
+    let ranges: Vec<String> = (0..size)
+        .map(|_| {
+            let start = rng.range(1..MAX_ID);
+            format!("{start}-{}", start + rng.range(0..MAX_ID / 100))
+        })
+        .collect();
+
+    let ingredients: Vec<String> = (0..size * 5)
+        .map(|_| rng.range(1..MAX_ID).to_string())
+        .collect();
+
+    format!("{}\n\n{}", ranges.join("\n"), ingredients.join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,12 +1,14 @@
 use advent_of_code::utils::{
     grid::Grid,
+    padded_grid::PaddedGrid,
     point::{DOWN, LEFT, Point, RIGHT},
 };
 
 advent_of_code::solution!(7);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut grid = Grid::parse(input);
+    // Empty border, so that splitters at the edges can split beams
+    let mut grid = Grid::parse(input).padded(1, b'.');
     let mut splits = 0;
 
     for y in 0..grid.height - 1 {
@@ -33,43 +35,65 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(splits)
 }
 
+/// A tile of the manifold, beams carry the number of timelines that reach them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cell {
+    Splitter,
+    Beams(u64),
+}
+
+impl TryFrom<u8> for Cell {
+    type Error = u8;
+
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
+        match b {
+            b'^' => Ok(Cell::Splitter),
+            b'S' => Ok(Cell::Beams(1)),
+            b'.' => Ok(Cell::Beams(0)),
+            _ => Err(b),
+        }
+    }
+}
+
+fn add_beams(grid: &mut PaddedGrid<Cell>, point: Point, count: u64) {
+    if let Cell::Beams(beams) = &mut grid[point] {
+        *beams += count;
+    }
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid = Grid::parse(input);
-    let mut counts = Grid::new(grid.width, grid.height, 0u64);
+    let mut grid = input.parse::<Grid<Cell>>().unwrap().padded(1, Cell::Beams(0));
 
     for y in 0..grid.height - 1 {
         for x in 0..grid.width {
             let point = Point::new(x, y);
 
-            let count = match grid[point] {
-                b'S' => 1,
-                b'|' => counts[point],
-                _ => 0,
+            let Cell::Beams(count) = grid[point] else {
+                continue;
             };
 
-            match grid[point] {
-                b'S' | b'|' => {
-                    let next = point + DOWN;
-                    match grid[next] {
-                        b'^' => {
-                            grid[next + LEFT] = b'|';
-                            grid[next + RIGHT] = b'|';
-                            counts[next + LEFT] += count;
-                            counts[next + RIGHT] += count;
-                        }
-                        _ => {
-                            grid[next] = b'|';
-                            counts[next] += count;
-                        }
-                    }
+            if count == 0 {
+                continue;
+            }
+
+            let next = point + DOWN;
+            match grid[next] {
+                Cell::Splitter => {
+                    add_beams(&mut grid, next + LEFT, count);
+                    add_beams(&mut grid, next + RIGHT, count);
                 }
-                _ => (),
+                Cell::Beams(_) => add_beams(&mut grid, next, count),
             }
         }
     }
 
-    let total = (0..counts.width)
-        .map(|x| counts[Point::new(x, counts.height - 1)])
+    let total = grid
+        .row(grid.height - 1)
+        .iter()
+        .map(|cell| match *cell {
+            Cell::Beams(count) => count,
+            Cell::Splitter => 0,
+        })
         .sum();
 
     Some(total)
@@ -1,6 +1,7 @@
+use advent_of_code::template::{generator::Rng, params::param};
 use advent_of_code::utils::{parse::parse_unsigned, point3d::Point3D};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, generator = generate_input);
 
 #[derive(Debug)]
 struct Pair {
@@ -75,7 +76,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         .map(|i| Circuit { parent: i, size: 1 })
         .collect();
 
-    for pair in pairs.iter().take(1000) {
+    for pair in pairs.iter().take(param("pairs", 1000)) {
         union(&mut circuits, pair.a, pair.b);
     }
 
@@ -118,6 +119,16 @@ pub fn part_two(input: &str) -> Option<u64> {
     None
 }
 
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|_| rng.range(0..100_000));
+            format!("{x},{y},{z}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
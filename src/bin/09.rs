@@ -3,7 +3,7 @@ use std::ops::RangeInclusive;
 use advent_of_code::utils::{parse::parse_unsigned, point::Point};
 // use rangemap::RangeInclusiveMap;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, variant_two = ("compressed", part_two_compressed));
 
 fn parse_input(input: &str) -> Vec<Point> {
     parse_unsigned(input)
@@ -261,6 +261,92 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(largest)
 }
 
+// Variants for `cargo solve 9 --variants`
+
+fn part_two_compressed(input: &str) -> Option<u64> {
+    // Compress coordinates so that every red tile lands on an odd index and the gaps
+    // between them on even ones. Everything reachable from the border is outside.
+    let points = parse_input(input);
+
+    let compress = |values: Vec<i64>| {
+        let mut values = values;
+        values.sort_unstable();
+        values.dedup();
+        values
+    };
+    let xs = compress(points.iter().map(|p| p.x).collect());
+    let ys = compress(points.iter().map(|p| p.y).collect());
+    let index = |values: &[i64], v: i64| 2 * values.binary_search(&v).unwrap() + 1;
+
+    let (width, height) = (2 * xs.len() + 1, 2 * ys.len() + 1);
+    let mut boundary = vec![false; width * height];
+
+    for (a, b) in points.iter().zip(points.iter().cycle().skip(1)) {
+        let (ax, bx) = (index(&xs, a.x), index(&xs, b.x));
+        let (ay, by) = (index(&ys, a.y), index(&ys, b.y));
+        for y in ay.min(by)..=ay.max(by) {
+            for x in ax.min(bx)..=ax.max(bx) {
+                boundary[y * width + x] = true;
+            }
+        }
+    }
+
+    let mut outside = vec![false; width * height];
+    let mut stack = vec![0];
+    outside[0] = true;
+    while let Some(i) = stack.pop() {
+        let (x, y) = (i % width, i / width);
+        let neighbors = [
+            (x > 0).then(|| i - 1),
+            (x + 1 < width).then(|| i + 1),
+            (y > 0).then(|| i - width),
+            (y + 1 < height).then(|| i + width),
+        ];
+        for n in neighbors.into_iter().flatten() {
+            if !outside[n] && !boundary[n] {
+                outside[n] = true;
+                stack.push(n);
+            }
+        }
+    }
+
+    // prefix[y][x] counts outside cells above and left of (x, y).
+    let mut prefix = vec![0u32; (width + 1) * (height + 1)];
+    for y in 0..height {
+        for x in 0..width {
+            prefix[(y + 1) * (width + 1) + x + 1] = u32::from(outside[y * width + x])
+                + prefix[y * (width + 1) + x + 1]
+                + prefix[(y + 1) * (width + 1) + x]
+                - prefix[y * (width + 1) + x];
+        }
+    }
+    let count_outside = |x0: usize, y0: usize, x1: usize, y1: usize| {
+        prefix[(y1 + 1) * (width + 1) + x1 + 1] + prefix[y0 * (width + 1) + x0]
+            - prefix[y0 * (width + 1) + x1 + 1]
+            - prefix[(y1 + 1) * (width + 1) + x0]
+    };
+
+    let mut largest = 0;
+
+    for i in 0..points.len() - 1 {
+        for j in i + 1..points.len() {
+            let (a, b) = (points[i], points[j]);
+            let size = (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1);
+            if size <= largest {
+                continue;
+            }
+
+            let (ax, bx) = (index(&xs, a.x), index(&xs, b.x));
+            let (ay, by) = (index(&ys, a.y), index(&ys, b.y));
+            if count_outside(ax.min(bx), ay.min(by), ax.max(bx), ay.max(by)) == 0 {
+                largest = size;
+            }
+        }
+    }
+
+    Some(largest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,4 +362,10 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(24));
     }
+
+    #[test]
+    fn test_part_two_compressed() {
+        let result = part_two_compressed(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(24));
+    }
 }
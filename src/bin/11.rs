@@ -137,7 +137,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples",
+            DAY,
+            advent_of_code::template::Part::One,
+        ));
         assert_eq!(result, Some(5));
     }
 
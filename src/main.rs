@@ -1,14 +1,19 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, benchmark, config, crosscheck, download, read, scaffold, solve, time,
+};
+use advent_of_code::template::cli;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, bench_args, Command};
+    use advent_of_code::template::config::{self, Config, CONFIG_PATH_ENV};
+    use advent_of_code::template::report::Report;
+    use advent_of_code::template::stress::parse_scales;
+    use advent_of_code::template::{Day, Part};
+    use std::env;
 
     pub enum AppArguments {
         Download {
@@ -26,72 +31,268 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            part: Option<Part>,
+            submit: Option<Part>,
+            variants: bool,
+            examples: bool,
         },
         All {
             release: bool,
+            part: Option<Part>,
+            report: Option<Report>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            part: Option<Part>,
+            scales: Option<Vec<usize>>,
+            report: Option<Report>,
+        },
+        Benchmark {
+            day: Day,
+            filter: Option<String>,
+            /// Flags that are forwarded to the bench target.
+            forwarded: Vec<String>,
+        },
+        Crosscheck {
+            day: Day,
+            cases: Option<u64>,
+            seed: Option<u64>,
+            max_size: Option<usize>,
         },
+        Config,
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+        Completions {
+            shell: String,
+        },
+        Help {
+            command: Option<&'static Command>,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let help = args.contains(["-h", "--help"]);
+        apply_overrides(&mut args)?;
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
+        let Some(name) = args.subcommand()? else {
+            if help {
+                return Ok(AppArguments::Help { command: None });
+            }
+            return Err(format!("no command specified.\n\n{}", cli::help()).into());
+        };
+
+        let command = cli::find(&name).ok_or_else(|| cli::unknown_command(&name))?;
+
+        if help {
+            return Ok(AppArguments::Help {
+                command: Some(command),
+            });
+        }
+
+        let app_args = match command.name {
+            "all" => {
+                let release = args.contains("--release") || Config::get().solve.release;
+                let part = args.opt_value_from_str("--part")?;
+                let report = parse_report(&mut args)?;
+                cli::positionals(command, args.finish())?;
+                AppArguments::All {
+                    release,
+                    part,
+                    report,
+                }
+            }
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let part = args.opt_value_from_str("--part")?;
+                let scales = args.opt_value_from_fn("--scale", parse_scales)?;
+                let report = parse_report(&mut args)?;
+                let free = cli::positionals(command, args.finish())?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: free.first().map(|day| parse_day(day)).transpose()?,
                     store,
+                    part,
+                    scales,
+                    report,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "download" => AppArguments::Download {
+                day: required_day(command, args)?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            "read" => AppArguments::Read {
+                day: required_day(command, args)?,
             },
+            "scaffold" => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                AppArguments::Scaffold {
+                    day: required_day(command, args)?,
+                    download,
+                    overwrite,
+                }
+            }
+            "solve" => {
+                let release = args.contains("--release") || Config::get().solve.release;
+                let part = args.opt_value_from_str("--part")?;
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat") || Config::get().solve.dhat;
+                let variants = args.contains("--variants");
+                let examples = args.contains("--examples");
+                AppArguments::Solve {
+                    day: required_day(command, args)?,
+                    release,
+                    dhat,
+                    part,
+                    submit,
+                    variants,
+                    examples,
+                }
+            }
+            "benchmark" => {
+                let mut forwarded = vec![];
+                for arg in bench_args::ALL {
+                    if let Some(value) = args.opt_value_from_str::<_, String>(arg.name)? {
+                        forwarded.extend([arg.name.to_string(), value]);
+                    }
+                }
+                let free = cli::positionals(command, args.finish())?;
+                let day = free
+                    .first()
+                    .ok_or_else(|| cli::missing_argument(command, "<day>"))?;
+
+                AppArguments::Benchmark {
+                    day: parse_day(day)?,
+                    filter: free.get(1).cloned(),
+                    forwarded,
+                }
+            }
+            "crosscheck" => {
+                let cases = args.opt_value_from_str("--cases")?;
+                let seed = args.opt_value_from_str("--seed")?;
+                let max_size = args.opt_value_from_str("--max-size")?;
+                AppArguments::Crosscheck {
+                    day: required_day(command, args)?,
+                    cases,
+                    seed,
+                    max_size,
+                }
+            }
+            "show-config" => {
+                cli::positionals(command, args.finish())?;
+                AppArguments::Config
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "today" => {
+                let wait = args.contains("--wait");
+                cli::positionals(command, args.finish())?;
+                AppArguments::Today { wait }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "completions" => {
+                let free = cli::positionals(command, args.finish())?;
+                let shell = free
+                    .first()
+                    .ok_or_else(|| cli::missing_argument(command, "<shell>"))?;
+                if !cli::SHELLS.contains(&shell.as_str()) {
+                    return Err(format!(
+                        "unsupported shell `{shell}`, expecting one of: {}.",
+                        cli::SHELLS.join(", ")
+                    )
+                    .into());
+                }
+                AppArguments::Completions {
+                    shell: shell.clone(),
+                }
             }
+            "help" => {
+                let free = cli::positionals(command, args.finish())?;
+                AppArguments::Help {
+                    command: free
+                        .first()
+                        .map(|name| cli::find(name).ok_or_else(|| cli::unknown_command(name)))
+                        .transpose()?,
+                }
+            }
+            // NOTE: every command of the definition is handled above.
+            name => unreachable!("command `{name}` is not implemented."),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        Ok(app_args)
+    }
+
+    /// Parses the `<day>` argument, which has to be the only positional argument left.
+    fn required_day(
+        command: &Command,
+        args: pico_args::Arguments,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        let free = cli::positionals(command, args.finish())?;
+        let day = free
+            .first()
+            .ok_or_else(|| cli::missing_argument(command, "<day>"))?;
+        parse_day(day)
+    }
+
+    fn parse_day(s: &str) -> Result<Day, Box<dyn std::error::Error>> {
+        s.parse()
+            .map_err(|e| format!("invalid day `{s}`: {e}.").into())
+    }
+
+    /// Applies the global `--config <path>` and `--set <key>=<value>` flags.
+    /// Overrides are passed on as environment variables, so that solution binaries spawned by commands see them too.
+    fn apply_overrides(args: &mut pico_args::Arguments) -> Result<(), Box<dyn std::error::Error>> {
+        let path: Option<String> = args.opt_value_from_str("--config")?;
+        let sets: Vec<String> = args.values_from_str("--set")?;
+
+        let mut vars = vec![];
+        if let Some(path) = path {
+            vars.push((CONFIG_PATH_ENV.to_string(), path));
         }
 
-        Ok(app_args)
+        for set in sets {
+            let (key, value) = set
+                .split_once('=')
+                .ok_or_else(|| format!("expected `--set <key>=<value>`, got `{set}`."))?;
+            if !config::is_known_key(key.trim()) {
+                return Err(format!("unknown configuration key `{}`.", key.trim()).into());
+            }
+            vars.push((config::env_var_name(key.trim()), value.trim().to_string()));
+        }
+
+        for (var, value) in vars {
+            // SAFETY: arguments are parsed at startup, before any other thread is spawned.
+            unsafe { env::set_var(var, value) };
+        }
+
+        Ok(())
+    }
+
+    fn parse_report(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Report>, Box<dyn std::error::Error>> {
+        let format = args.opt_value_from_str("--report")?;
+        let out = args.opt_value_from_str("--out")?;
+
+        let default_format = Config::get().output.report_format.as_deref();
+
+        match (format, out) {
+            (Some(format), out) => Ok(Some(Report { format, out })),
+            (None, Some(out)) => match default_format {
+                Some(format) => Ok(Some(Report {
+                    format: format.parse()?,
+                    out: Some(out),
+                })),
+                None => {
+                    Err("`--out` requires `--report <format>` or `output.report_format`.".into())
+                }
+            },
+            (None, None) => Ok(None),
+        }
     }
 }
 
@@ -102,8 +303,19 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                part,
+                report,
+            } => all::handle(release, part, report),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                part,
+                scales,
+                report,
+            } => time::handle(day, all, store, part, scales, report),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -120,25 +332,33 @@ fn main() {
                 day,
                 release,
                 dhat,
+                part,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                variants,
+                examples,
+            } => solve::handle(day, release, dhat, part, submit, variants, examples),
+            AppArguments::Benchmark {
+                day,
+                filter,
+                forwarded,
+            } => benchmark::handle(day, filter, &forwarded),
+            AppArguments::Crosscheck {
+                day,
+                cases,
+                seed,
+                max_size,
+            } => crosscheck::handle(day, cases, seed, max_size),
+            AppArguments::Config => config::handle(),
+            AppArguments::Completions { shell } => {
+                // NOTE: the shell was validated while parsing.
+                print!("{}", cli::completions(&shell).unwrap_or_default());
             }
+            AppArguments::Help { command } => match command {
+                Some(command) => print!("{}", cli::command_help(command)),
+                None => print!("{}", cli::help()),
+            },
+            #[cfg(feature = "today")]
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
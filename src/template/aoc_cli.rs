@@ -4,7 +4,8 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::config::Config;
+use crate::template::{Day, Part};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -72,7 +73,8 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer. Without a configured year, aoc-cli falls back to its own default.
+pub fn submit(day: Day, part: Part, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
@@ -81,18 +83,16 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    format!("{}/{day}.txt", Config::get().paths.inputs.display())
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    format!("{}/{day}.md", Config::get().paths.puzzles.display())
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+/// Reads the event year from the `year` setting (or `AOC_YEAR`), if set.
+pub fn get_year() -> Option<u16> {
+    Config::get().year
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -103,6 +103,11 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
         cmd_args.push(year.to_string());
     }
 
+    if let Some(session_file) = &Config::get().session_file {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file.display().to_string());
+    }
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
@@ -0,0 +1,667 @@
+/// Statistically rigorous benchmarks in the spirit of criterion, for optimization work on a day.
+/// `scaffold` generates a bench target per day in `benches/`, run it with `cargo benchmark <day>`.
+///
+/// Every function is sampled repeatedly, its mean time is estimated with a bootstrapped confidence
+/// interval and compared against a saved baseline. Baselines and HTML reports live in
+/// `<target dir>/aoc-bench/<day>/`.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Write as _,
+    fs,
+    hint::black_box,
+    io::{Write as _, stdout},
+    path::PathBuf,
+    process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use pico_args::Arguments;
+use tinyjson::JsonValue;
+
+use crate::template::cli::{self, bench_args};
+use crate::template::generator::Rng;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+/// Relative changes of the mean that are smaller than this are considered noise.
+const NOISE_THRESHOLD: f64 = 0.02;
+const BOOTSTRAP_RESAMPLES: usize = 10_000;
+const CONFIDENCE_LEVEL: f64 = 0.95;
+const DEFAULT_BASELINE: &str = "base";
+
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Number of samples per function.
+    pub sample_size: usize,
+    pub warm_up_time: Duration,
+    /// Time spent collecting samples, per function.
+    pub measurement_time: Duration,
+    /// Only run functions whose name contains this string.
+    pub filter: Option<String>,
+    /// Baseline that results are compared against and then saved as.
+    pub save_baseline: String,
+    /// Baseline that results are compared against without overwriting it.
+    pub baseline: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            sample_size: 100,
+            warm_up_time: Duration::from_secs(1),
+            measurement_time: Duration::from_secs(3),
+            filter: None,
+            save_baseline: DEFAULT_BASELINE.into(),
+            baseline: None,
+        }
+    }
+}
+
+/// A point estimate with the bounds of its confidence interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    pub point: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Samples that lie outside of Tukey's fences, see [`Outliers::classify`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+/// Measurements of a single function.
+struct Report {
+    name: String,
+    /// Time per iteration of every sample, in nanoseconds.
+    samples: Vec<f64>,
+    iterations: u64,
+    mean: Estimate,
+    baseline: Option<Vec<f64>>,
+    change: Option<Estimate>,
+}
+
+/// Runs the benchmarks of a day. Create it with [`Bench::from_env`], register functions with
+/// [`Bench::function`] and write the report with [`Bench::finish`].
+pub struct Bench {
+    day: Day,
+    options: Options,
+    rng: Rng,
+    reports: Vec<Report>,
+}
+
+impl Bench {
+    pub fn new(day: Day, options: Options) -> Self {
+        Self {
+            day,
+            options,
+            rng: Rng::new(u64::from(day.into_inner())),
+            reports: vec![],
+        }
+    }
+
+    /// Creates a bench with options parsed from the command line. Exits on invalid arguments.
+    pub fn from_env(day: Day) -> Self {
+        let mut args = Arguments::from_env();
+        let defaults = Options::default();
+
+        // cargo passes `--bench` to every bench target.
+        args.contains("--bench");
+
+        let options = Options {
+            sample_size: parse_arg(args.opt_value_from_str(bench_args::SAMPLE_SIZE.name))
+                .filter(|n: &usize| *n >= 2)
+                .unwrap_or(defaults.sample_size),
+            warm_up_time: parse_arg(args.opt_value_from_str(bench_args::WARM_UP_TIME.name))
+                .map_or(defaults.warm_up_time, Duration::from_secs_f64),
+            measurement_time: parse_arg(args.opt_value_from_str(bench_args::MEASUREMENT_TIME.name))
+                .map_or(defaults.measurement_time, Duration::from_secs_f64),
+            save_baseline: parse_arg(args.opt_value_from_str(bench_args::SAVE_BASELINE.name))
+                .unwrap_or(defaults.save_baseline),
+            baseline: parse_arg(args.opt_value_from_str(bench_args::BASELINE.name)),
+            filter: None,
+        };
+
+        let free: Vec<String> = args
+            .finish()
+            .into_iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+
+        // at most one free argument, the filter.
+        if let Some(arg) = free.iter().find(|a| a.starts_with('-')).or(free.get(1)) {
+            let mut message = format!("Unexpected command-line input: `{arg}`.");
+            let names = bench_args::ALL.iter().map(|a| a.name);
+            if let Some(suggestion) = cli::suggest(arg, names) {
+                let _ = write!(message, " Did you mean `{suggestion}`?");
+            }
+            eprintln!("{message}");
+            process::exit(1);
+        }
+
+        Self::new(
+            day,
+            Options {
+                filter: free.into_iter().next(),
+                ..options
+            },
+        )
+    }
+
+    pub fn day(&self) -> Day {
+        self.day
+    }
+
+    /// Benchmarks `func`, unless it is excluded by the filter.
+    pub fn function<T>(&mut self, name: &str, mut func: impl FnMut() -> T) -> &mut Self {
+        if let Some(filter) = &self.options.filter
+            && !name.contains(filter.as_str())
+        {
+            return self;
+        }
+
+        let id = format!("day{}/{name}", self.day);
+        println!("{ANSI_BOLD}{id}{ANSI_RESET}");
+        print!("{:24}{ANSI_ITALIC}warming up...{ANSI_RESET}", "");
+        let _ = stdout().flush();
+
+        let (samples, iterations) = self.sample(&mut func);
+
+        let compare_to = self
+            .options
+            .baseline
+            .as_ref()
+            .unwrap_or(&self.options.save_baseline);
+        let baseline = match load_samples(&self.baseline_path(name, compare_to)) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("Ignoring baseline `{compare_to}`: {e}");
+                None
+            }
+        };
+
+        let mean = bootstrap(&mut self.rng, &samples, None);
+        let change = baseline
+            .as_ref()
+            .map(|baseline| bootstrap(&mut self.rng, &samples, Some(baseline)));
+
+        print_summary(&samples, iterations, &mean, change.as_ref());
+
+        if self.options.baseline.is_none() {
+            let path = self.baseline_path(name, &self.options.save_baseline);
+            if let Err(e) = store_samples(&path, &samples) {
+                eprintln!("Failed to save baseline to \"{}\": {e}", path.display());
+            }
+        }
+
+        self.reports.push(Report {
+            name: name.to_string(),
+            samples,
+            iterations,
+            mean,
+            baseline,
+            change,
+        });
+
+        self
+    }
+
+    /// Writes the HTML report of all benchmarked functions.
+    pub fn finish(&self) {
+        if self.reports.is_empty() {
+            println!("No functions matched the filter.");
+            return;
+        }
+
+        let path = self.directory().join("report.html");
+        let html = render_html(self.day, &self.reports);
+        match fs::create_dir_all(self.directory()).and_then(|()| fs::write(&path, html)) {
+            Ok(()) => println!("Report written to \"{}\".", path.display()),
+            Err(e) => eprintln!("Failed to write report to \"{}\": {e}", path.display()),
+        }
+    }
+
+    /// Collects per-iteration times of `func`. Returns the samples and iterations per sample.
+    fn sample<T>(&self, func: &mut impl FnMut() -> T) -> (Vec<f64>, u64) {
+        let options = &self.options;
+
+        // warm up with exponentially growing batches to estimate the time per iteration.
+        let mut iterations: u64 = 0;
+        let mut batch: u64 = 1;
+        let start = Instant::now();
+        while start.elapsed() < options.warm_up_time {
+            for _ in 0..batch {
+                black_box(func());
+            }
+            iterations += batch;
+            batch *= 2;
+        }
+        let per_iteration = start.elapsed().as_nanos() as f64 / iterations as f64;
+
+        let per_sample = options.measurement_time.as_nanos() as f64 / options.sample_size as f64;
+        let iterations = ((per_sample / per_iteration) as u64).max(1);
+
+        if per_iteration * options.sample_size as f64
+            > 2.0 * options.measurement_time.as_nanos() as f64
+        {
+            eprintln!(
+                "Warning: unable to complete {} samples in {:?}, this will take about {:.1?}. \
+                 Consider passing `{}`.",
+                options.sample_size,
+                options.measurement_time,
+                Duration::from_nanos((per_iteration * options.sample_size as f64) as u64),
+                bench_args::SAMPLE_SIZE.name
+            );
+        }
+
+        let samples = (0..options.sample_size)
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..iterations {
+                    black_box(func());
+                }
+                start.elapsed().as_nanos() as f64 / iterations as f64
+            })
+            .collect();
+
+        (samples, iterations)
+    }
+
+    fn directory(&self) -> PathBuf {
+        let target = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+        PathBuf::from(target)
+            .join("aoc-bench")
+            .join(self.day.to_string())
+    }
+
+    fn baseline_path(&self, name: &str, baseline: &str) -> PathBuf {
+        self.directory().join(name).join(format!("{baseline}.json"))
+    }
+}
+
+/// Unwraps a parsed command-line argument, exiting on malformed values.
+fn parse_arg<T>(result: Result<T, pico_args::Error>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("Unexpected command-line input: {e}.");
+        process::exit(1);
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+fn std_dev(samples: &[f64]) -> f64 {
+    let mean = mean(samples);
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>()
+        / (samples.len() as f64 - 1.0).max(1.0);
+    variance.sqrt()
+}
+
+/// Linearly interpolated percentile of sorted values, `p` in `0.0..=1.0`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+fn sorted(samples: &[f64]) -> Vec<f64> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    sorted
+}
+
+/// Estimates the mean of `samples` or, with a `baseline`, the relative change of the mean.
+fn bootstrap(rng: &mut Rng, samples: &[f64], baseline: Option<&[f64]>) -> Estimate {
+    let statistic = |samples: &[f64], baseline: Option<&[f64]>| match baseline {
+        Some(baseline) => mean(samples) / mean(baseline) - 1.0,
+        None => mean(samples),
+    };
+
+    let mut resample = |values: &[f64], buffer: &mut Vec<f64>| {
+        buffer.clear();
+        buffer.extend((0..values.len()).map(|_| values[rng.index(values.len())]));
+    };
+
+    let (mut a, mut b) = (vec![], vec![]);
+    let mut estimates: Vec<f64> = (0..BOOTSTRAP_RESAMPLES)
+        .map(|_| {
+            resample(samples, &mut a);
+            if let Some(baseline) = baseline {
+                resample(baseline, &mut b);
+            }
+            statistic(&a, baseline.map(|_| b.as_slice()))
+        })
+        .collect();
+    estimates.sort_unstable_by(f64::total_cmp);
+
+    let tail = (1.0 - CONFIDENCE_LEVEL) / 2.0;
+    Estimate {
+        point: statistic(samples, baseline),
+        lower: percentile(&estimates, tail),
+        upper: percentile(&estimates, 1.0 - tail),
+    }
+}
+
+impl Outliers {
+    /// Classifies samples using Tukey's fences: mild outliers lie more than 1.5, severe ones
+    /// more than 3 interquartile ranges outside of the quartiles.
+    pub fn classify(samples: &[f64]) -> Self {
+        let sorted = sorted(samples);
+        let (q1, q3) = (percentile(&sorted, 0.25), percentile(&sorted, 0.75));
+        let iqr = q3 - q1;
+
+        let mut outliers = Outliers::default();
+        for &sample in samples {
+            if sample < q1 - 3.0 * iqr {
+                outliers.low_severe += 1;
+            } else if sample < q1 - 1.5 * iqr {
+                outliers.low_mild += 1;
+            } else if sample > q3 + 3.0 * iqr {
+                outliers.high_severe += 1;
+            } else if sample > q3 + 1.5 * iqr {
+                outliers.high_mild += 1;
+            }
+        }
+        outliers
+    }
+
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+}
+
+/// Describes a relative change of the mean, taking the noise threshold into account.
+fn verdict(change: &Estimate) -> &'static str {
+    if change.lower > NOISE_THRESHOLD {
+        "Performance has regressed."
+    } else if change.upper < -NOISE_THRESHOLD {
+        "Performance has improved."
+    } else if change.lower > 0.0 || change.upper < 0.0 {
+        "Change within noise threshold."
+    } else {
+        "No change in performance detected."
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Formats nanoseconds with a fitting unit and four significant digits, e.g. `1.234 µs`.
+fn format_nanos(nanos: f64) -> String {
+    let (value, unit) = match nanos.abs() {
+        n if n < 1e3 => (nanos, "ns"),
+        n if n < 1e6 => (nanos / 1e3, "µs"),
+        n if n < 1e9 => (nanos / 1e6, "ms"),
+        _ => (nanos / 1e9, "s"),
+    };
+
+    let precision = match value.abs() {
+        v if v < 10.0 => 3,
+        v if v < 100.0 => 2,
+        _ => 1,
+    };
+    format!("{value:.precision$} {unit}")
+}
+
+fn format_change(change: f64) -> String {
+    format!("{:+.2}%", change * 100.0)
+}
+
+fn print_summary(samples: &[f64], iterations: u64, mean: &Estimate, change: Option<&Estimate>) {
+    // overwrite the progress line.
+    print!("\r\x1b[2K");
+    println!(
+        "{:24}time:   [{} {ANSI_BOLD}{}{ANSI_RESET} {}]",
+        "",
+        format_nanos(mean.lower),
+        format_nanos(mean.point),
+        format_nanos(mean.upper)
+    );
+
+    if let Some(change) = change {
+        println!(
+            "{:24}change: [{} {ANSI_BOLD}{}{ANSI_RESET} {}]",
+            "",
+            format_change(change.lower),
+            format_change(change.point),
+            format_change(change.upper)
+        );
+        println!("{:24}{}", "", verdict(change));
+    }
+
+    let outliers = Outliers::classify(samples);
+    if outliers.total() > 0 {
+        let percent = |n: usize| n as f64 / samples.len() as f64 * 100.0;
+        println!(
+            "Found {} outliers among {} measurements ({:.2}%)",
+            outliers.total(),
+            samples.len(),
+            percent(outliers.total())
+        );
+        for (count, kind) in [
+            (outliers.low_severe, "low severe"),
+            (outliers.low_mild, "low mild"),
+            (outliers.high_mild, "high mild"),
+            (outliers.high_severe, "high severe"),
+        ] {
+            if count > 0 {
+                println!("  {count} ({:.2}%) {kind}", percent(count));
+            }
+        }
+    }
+
+    println!(
+        "{ANSI_ITALIC}{} samples of {iterations} iteration(s){ANSI_RESET}\n",
+        samples.len()
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn load_samples(path: &PathBuf) -> Result<Option<Vec<f64>>, String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Ok(None);
+    };
+
+    let json = JsonValue::from_str(&contents).map_err(|_| "not a valid JSON file.")?;
+    json.get::<HashMap<String, JsonValue>>()
+        .and_then(|o| o.get("samples"))
+        .and_then(|s| s.get::<Vec<JsonValue>>())
+        .and_then(|s| s.iter().map(|v| v.get::<f64>().copied()).collect())
+        .filter(|s: &Vec<f64>| s.len() >= 2)
+        .map(Some)
+        .ok_or_else(|| "expected `samples` to be an array of numbers.".into())
+}
+
+fn store_samples(path: &PathBuf, samples: &[f64]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = JsonValue::Object(HashMap::from([(
+        "samples".to_string(),
+        JsonValue::Array(samples.iter().map(|s| JsonValue::Number(*s)).collect()),
+    )]));
+    fs::write(path, json.stringify().unwrap_or_default())
+}
+
+/* -------------------------------------------------------------------------- */
+
+const PLOT_WIDTH: f64 = 640.0;
+const PLOT_ROW: f64 = 40.0;
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn render_html(day: Day, reports: &[Report]) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Day {day} benchmarks</title>\n\
+         <style>\
+         body {{ font-family: sans-serif; margin: 2em auto; max-width: 720px; }}\
+         table {{ border-collapse: collapse; margin-bottom: 1em; }}\
+         td, th {{ padding: 0.2em 0.8em; text-align: right; border-bottom: 1px solid #ddd; }}\
+         th {{ text-align: left; }}\
+         </style>\n</head>\n<body>\n<h1>Day {day} benchmarks</h1>\n"
+    );
+
+    for report in reports {
+        let sorted = sorted(&report.samples);
+        let outliers = Outliers::classify(&report.samples);
+        let _ = write!(
+            html,
+            "<h2>{}</h2>\n<table>\n\
+             <tr><th></th><th>Lower bound</th><th>Estimate</th><th>Upper bound</th></tr>\n\
+             <tr><th>Mean</th><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&report.name),
+            format_nanos(report.mean.lower),
+            format_nanos(report.mean.point),
+            format_nanos(report.mean.upper)
+        );
+        if let Some(change) = &report.change {
+            let _ = writeln!(
+                html,
+                "<tr><th>Change</th><td>{}</td><td>{}</td><td>{}</td></tr>",
+                format_change(change.lower),
+                format_change(change.point),
+                format_change(change.upper)
+            );
+        }
+        let _ = write!(
+            html,
+            "</table>\n<table>\n\
+             <tr><th>Median</th><td>{}</td></tr>\n\
+             <tr><th>Std. dev.</th><td>{}</td></tr>\n\
+             <tr><th>Samples</th><td>{} &times; {} iteration(s)</td></tr>\n\
+             <tr><th>Outliers</th><td>{}</td></tr>\n</table>\n",
+            format_nanos(percentile(&sorted, 0.5)),
+            format_nanos(std_dev(&report.samples)),
+            report.samples.len(),
+            report.iterations,
+            outliers.total()
+        );
+        if let Some(change) = &report.change {
+            let _ = writeln!(html, "<p>{}</p>", verdict(change));
+        }
+        render_plot(&mut html, report);
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Plots the samples (and those of the baseline) on a shared time axis.
+fn render_plot(html: &mut String, report: &Report) {
+    let mut rows = vec![("current", "#1f77b4", report.samples.as_slice())];
+    if let Some(baseline) = &report.baseline {
+        rows.push(("baseline", "#d62728", baseline.as_slice()));
+    }
+
+    let all = rows.iter().flat_map(|(_, _, samples)| samples.iter());
+    let min = all.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = all.copied().fold(f64::NEG_INFINITY, f64::max);
+    let x =
+        |nanos: f64| 80.0 + (nanos - min) / (max - min).max(f64::EPSILON) * (PLOT_WIDTH - 100.0);
+
+    let height = PLOT_ROW * rows.len() as f64 + 20.0;
+    let _ = writeln!(
+        html,
+        "<svg width=\"{PLOT_WIDTH}\" height=\"{height}\" font-size=\"12\">"
+    );
+
+    for (i, (label, color, samples)) in rows.iter().enumerate() {
+        let y = PLOT_ROW * (i as f64 + 0.5);
+        let _ = writeln!(html, "<text x=\"0\" y=\"{y}\" dy=\"4\">{label}</text>");
+        for &sample in *samples {
+            let _ = writeln!(
+                html,
+                "<circle cx=\"{:.1}\" cy=\"{y}\" r=\"3\" fill=\"{color}\" fill-opacity=\"0.4\"/>",
+                x(sample)
+            );
+        }
+        let mean_x = x(mean(samples));
+        let _ = writeln!(
+            html,
+            "<line x1=\"{mean_x:.1}\" x2=\"{mean_x:.1}\" y1=\"{}\" y2=\"{}\" stroke=\"{color}\" stroke-width=\"2\"/>",
+            y - PLOT_ROW / 3.0,
+            y + PLOT_ROW / 3.0
+        );
+    }
+
+    let axis_y = height - 4.0;
+    let _ = writeln!(
+        html,
+        "<text x=\"80\" y=\"{axis_y}\">{}</text>\n\
+         <text x=\"{}\" y=\"{axis_y}\" text-anchor=\"end\">{}</text>\n</svg>",
+        format_nanos(min),
+        PLOT_WIDTH - 20.0,
+        format_nanos(max)
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Estimate, Outliers, bootstrap, format_nanos, percentile, verdict};
+    use crate::template::generator::Rng;
+
+    #[test]
+    fn interpolates_percentiles() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 0.5), 2.5);
+        assert_eq!(percentile(&sorted, 1.0), 4.0);
+    }
+
+    #[test]
+    fn classifies_outliers() {
+        let mut samples: Vec<f64> = (1..=20).map(f64::from).collect();
+        samples.extend([35.0, 100.0, -20.0]);
+        let outliers = Outliers::classify(&samples);
+        assert_eq!(outliers.high_mild, 1);
+        assert_eq!(outliers.high_severe, 1);
+        assert_eq!(outliers.low_mild, 1);
+        assert_eq!(outliers.total(), 3);
+    }
+
+    #[test]
+    fn bootstraps_confidence_intervals() {
+        let mut rng = Rng::new(1);
+        let samples: Vec<f64> = (0..100).map(|i| 100.0 + f64::from(i % 10)).collect();
+        let mean = bootstrap(&mut rng, &samples, None);
+        assert_eq!(mean.point, 104.5);
+        assert!(mean.lower < mean.point && mean.point < mean.upper);
+
+        let faster: Vec<f64> = samples.iter().map(|s| s / 2.0).collect();
+        let change = bootstrap(&mut rng, &faster, Some(&samples));
+        assert_eq!(change.point, -0.5);
+        assert_eq!(verdict(&change), "Performance has improved.");
+    }
+
+    #[test]
+    fn ignores_changes_within_noise() {
+        let change = Estimate {
+            point: 0.01,
+            lower: 0.005,
+            upper: 0.015,
+        };
+        assert_eq!(verdict(&change), "Change within noise threshold.");
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_nanos(12.5), "12.50 ns");
+        assert_eq!(format_nanos(1_234.0), "1.234 µs");
+        assert_eq!(format_nanos(123_250_000.0), "123.2 ms");
+    }
+}
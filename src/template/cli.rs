@@ -0,0 +1,772 @@
+/// Declarative definition of the command-line interface.
+/// Help output, argument validation with suggestions and shell completions are all generated from it.
+use std::ffi::OsString;
+use std::fmt::Write as _;
+
+/// A flag or positional argument.
+#[derive(Clone, Copy, Debug)]
+pub struct Arg {
+    /// `--flag`, or `<name>` / `[name]` for a required / optional positional argument.
+    pub name: &'static str,
+    /// Placeholder of the flag's value, e.g. `<format>`. `None` for switches and positional arguments.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+    /// Values offered by shell completions.
+    pub choices: &'static [&'static str],
+}
+
+impl Arg {
+    const fn switch(name: &'static str, help: &'static str) -> Self {
+        Arg {
+            name,
+            value: None,
+            help,
+            choices: &[],
+        }
+    }
+
+    const fn option(name: &'static str, value: &'static str, help: &'static str) -> Self {
+        Arg {
+            name,
+            value: Some(value),
+            help,
+            choices: &[],
+        }
+    }
+
+    const fn positional(name: &'static str, help: &'static str) -> Self {
+        Arg {
+            name,
+            value: None,
+            help,
+            choices: &[],
+        }
+    }
+
+    const fn choices(self, choices: &'static [&'static str]) -> Self {
+        Arg { choices, ..self }
+    }
+
+    pub fn is_positional(&self) -> bool {
+        !self.name.starts_with('-')
+    }
+
+    fn label(&self) -> String {
+        match self.value {
+            Some(value) => format!("{} {value}", self.name),
+            None => self.name.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub args: &'static [Arg],
+}
+
+/* -------------------------------------------------------------------------- */
+
+const DAY: Arg = Arg::positional("<day>", "Day number, e.g. `1` or `01`.");
+const RELEASE: Arg = Arg::switch("--release", "Build the solution in release mode.");
+const REPORT: Arg = Arg::option(
+    "--report",
+    "<format>",
+    "Export results as csv, json, markdown or junit (needs data/answers.json).",
+)
+.choices(&["csv", "json", "markdown", "junit"]);
+const OUT: Arg = Arg::option(
+    "--out",
+    "<path>",
+    "Write the report to a file instead of stdout.",
+);
+
+/// Flags accepted by solution binaries. Commands forward these when they spawn a solution.
+pub mod solution_args {
+    use super::Arg;
+
+    pub const TIME: Arg = Arg::switch("--time", "Benchmark every part.");
+    pub const SUBMIT: Arg = Arg::option(
+        "--submit",
+        "<part>",
+        "Submit the answer of a part via aoc-cli.",
+    )
+    .choices(&["1", "2"]);
+    pub const PART: Arg =
+        Arg::option("--part", "<part>", "Only run this part.").choices(&["1", "2"]);
+    pub const CROSSCHECK: Arg = Arg::switch(
+        "--crosscheck",
+        "Compare parts against their reference implementations.",
+    );
+    pub const CASES: Arg = Arg::option(
+        "--cases",
+        "<n>",
+        "Number of generated cases. [default: 1000]",
+    );
+    pub const SEED: Arg = Arg::option(
+        "--seed",
+        "<n>",
+        "Seed of the input generator. [default: 2025]",
+    );
+    pub const MAX_SIZE: Arg = Arg::option(
+        "--max-size",
+        "<n>",
+        "Largest size passed to the input generator. [default: 20]",
+    );
+    pub const VARIANTS: Arg = Arg::switch(
+        "--variants",
+        "Compare the timings and answers of every part's variants.",
+    );
+    pub const EXAMPLES: Arg = Arg::switch(
+        "--examples",
+        "Check every part against the examples listed in the day's example manifest.",
+    );
+    pub const SCALE: Arg = Arg::option(
+        "--scale",
+        "<factors>",
+        "Benchmark generated inputs at these comma-separated scales, e.g. `1,2,4,8`.",
+    );
+
+    pub const ALL: &[Arg] = &[
+        TIME, SUBMIT, PART, CROSSCHECK, CASES, SEED, MAX_SIZE, VARIANTS, EXAMPLES, SCALE,
+    ];
+}
+
+/// Flags accepted by the bench targets in `benches/`. The `benchmark` command forwards these.
+pub mod bench_args {
+    use super::Arg;
+
+    pub const SAVE_BASELINE: Arg = Arg::option(
+        "--save-baseline",
+        "<name>",
+        "Compare against this baseline and save the results as it. [default: base]",
+    );
+    pub const BASELINE: Arg = Arg::option(
+        "--baseline",
+        "<name>",
+        "Compare against this baseline without overwriting it.",
+    );
+    pub const SAMPLE_SIZE: Arg = Arg::option(
+        "--sample-size",
+        "<n>",
+        "Number of samples per function. [default: 100]",
+    );
+    pub const WARM_UP_TIME: Arg = Arg::option(
+        "--warm-up-time",
+        "<seconds>",
+        "Time spent warming up each function. [default: 1]",
+    );
+    pub const MEASUREMENT_TIME: Arg = Arg::option(
+        "--measurement-time",
+        "<seconds>",
+        "Time spent sampling each function. [default: 3]",
+    );
+
+    pub const ALL: &[Arg] = &[
+        SAVE_BASELINE,
+        BASELINE,
+        SAMPLE_SIZE,
+        WARM_UP_TIME,
+        MEASUREMENT_TIME,
+    ];
+}
+
+use solution_args::{CASES, MAX_SIZE, PART, SCALE, SEED};
+
+/// Flags accepted before or after any command.
+pub const GLOBAL_ARGS: &[Arg] = &[
+    Arg::option(
+        "--config",
+        "<path>",
+        "Read the configuration from this file.",
+    ),
+    Arg::option(
+        "--set",
+        "<key=value>",
+        "Override a configuration value, can be repeated.",
+    ),
+    Arg::switch("--help", "Print help."),
+];
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Create the solution, input and example files for a day.",
+        args: &[
+            DAY,
+            Arg::switch("--download", "Download the input and puzzle afterwards."),
+            Arg::switch("--overwrite", "Overwrite an existing solution file."),
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Download the input and puzzle description of a day.",
+        args: &[DAY],
+    },
+    Command {
+        name: "read",
+        about: "Read the puzzle description of a day in the terminal.",
+        args: &[DAY],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution of a day against its input.",
+        args: &[
+            DAY,
+            RELEASE,
+            Arg::switch("--dhat", "Profile heap allocations with dhat."),
+            PART,
+            solution_args::SUBMIT,
+            solution_args::VARIANTS,
+            solution_args::EXAMPLES,
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Run the solutions of all days.",
+        args: &[RELEASE, PART, REPORT, OUT],
+    },
+    Command {
+        name: "time",
+        about: "Benchmark days, by default the ones without stored timings.",
+        args: &[
+            Arg::positional("[day]", "Only benchmark this day."),
+            Arg::switch("--all", "Benchmark all days."),
+            Arg::switch("--store", "Store the timings and update the README."),
+            PART,
+            SCALE,
+            REPORT,
+            OUT,
+        ],
+    },
+    Command {
+        name: "benchmark",
+        about: "Run the bench target of a day and compare against a saved baseline.",
+        args: &[
+            DAY,
+            Arg::positional(
+                "[filter]",
+                "Only benchmark functions whose name contains this.",
+            ),
+            bench_args::SAVE_BASELINE,
+            bench_args::BASELINE,
+            bench_args::SAMPLE_SIZE,
+            bench_args::WARM_UP_TIME,
+            bench_args::MEASUREMENT_TIME,
+        ],
+    },
+    Command {
+        name: "crosscheck",
+        about: "Compare the parts of a day against their reference implementations.",
+        args: &[DAY, CASES, SEED, MAX_SIZE],
+    },
+    Command {
+        name: "show-config",
+        about: "Print the resolved configuration and where each value comes from.",
+        args: &[],
+    },
+    #[cfg(feature = "today")]
+    Command {
+        name: "today",
+        about: "Scaffold, download and read the puzzle of today.",
+        args: &[Arg::switch(
+            "--wait",
+            "Wait for the next puzzle to unlock first.",
+        )],
+    },
+    Command {
+        name: "completions",
+        about: "Print a completion script for bash, zsh or fish.",
+        args: &[Arg::positional("<shell>", "Shell to generate completions for.").choices(SHELLS)],
+    },
+    Command {
+        name: "help",
+        about: "Print help for all commands or a single one.",
+        args: &[Arg::positional("[command]", "Command to print help for.")],
+    },
+];
+
+/// Looks up a command by name.
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/// Error for a command that does not exist, with a suggestion if one is close.
+pub fn unknown_command(name: &str) -> String {
+    let mut message = format!("unknown command `{name}`.");
+    if let Some(suggestion) = suggest(name, COMMANDS.iter().map(|c| c.name)) {
+        let _ = write!(message, " Did you mean `{suggestion}`?");
+    }
+    message.push_str("\n\nFor a list of commands, try `--help`.");
+    message
+}
+
+/// Error for a required positional argument that was not passed.
+pub fn missing_argument(command: &Command, name: &str) -> String {
+    format!(
+        "missing argument `{name}`.\n\n{}\n\nFor more information, try `cargo {} --help`.",
+        usage(command),
+        command.name
+    )
+}
+
+/// Returns the positional arguments of `command` from what is left after parsing its flags.
+/// Fails on unknown flags and surplus arguments.
+pub fn positionals(command: &Command, remaining: Vec<OsString>) -> Result<Vec<String>, String> {
+    let expected = command.args.iter().filter(|a| a.is_positional()).count();
+    let mut values = vec![];
+
+    for arg in remaining {
+        let arg = arg.to_string_lossy().into_owned();
+        if arg.starts_with('-') || values.len() == expected {
+            return Err(unexpected_argument(command, &arg));
+        }
+        values.push(arg);
+    }
+
+    Ok(values)
+}
+
+fn unexpected_argument(command: &Command, arg: &str) -> String {
+    let mut message = format!("unexpected argument `{arg}`.");
+
+    if arg.starts_with('-') {
+        let names = flags(command).map(|a| a.name);
+        if let Some(suggestion) = suggest(arg, names) {
+            let _ = write!(message, " Did you mean `{suggestion}`?");
+        }
+    }
+
+    let _ = write!(
+        message,
+        "\n\n{}\n\nFor more information, try `cargo {} --help`.",
+        usage(command),
+        command.name
+    );
+    message
+}
+
+/// Returns the closest candidate within a small edit distance, or one that starts with `input`.
+pub fn suggest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(input, c), c))
+        .filter(|(distance, c)| *distance <= 2 || (input.len() >= 3 && c.starts_with(input)))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn usage(command: &Command) -> String {
+    let mut usage = format!("Usage: cargo {}", command.name);
+    for arg in command.args {
+        if arg.is_positional() {
+            let _ = write!(usage, " {}", arg.name);
+        } else {
+            let _ = write!(usage, " [{}]", arg.label());
+        }
+    }
+    usage
+}
+
+fn write_args(s: &mut String, title: &str, args: &[&Arg]) {
+    if args.is_empty() {
+        return;
+    }
+
+    let width = args.iter().map(|a| a.label().len()).max().unwrap_or(0);
+    let _ = writeln!(s, "\n{title}:");
+    for arg in args {
+        let _ = writeln!(s, "  {:width$}  {}", arg.label(), arg.help);
+    }
+}
+
+/// Help for a single command.
+pub fn command_help(command: &Command) -> String {
+    let mut help = format!("{}\n\n{}\n", command.about, usage(command));
+
+    let (positional, flags): (Vec<&Arg>, Vec<&Arg>) =
+        command.args.iter().partition(|a| a.is_positional());
+    write_args(&mut help, "Arguments", &positional);
+    write_args(&mut help, "Options", &flags);
+    write_args(
+        &mut help,
+        "Global options",
+        &GLOBAL_ARGS.iter().collect::<Vec<_>>(),
+    );
+
+    help
+}
+
+/// Help listing every command.
+pub fn help() -> String {
+    let mut help = String::from(
+        "Advent of Code template commands.\n\nUsage: cargo <command> [options]\n\nCommands:\n",
+    );
+
+    let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for command in COMMANDS {
+        let _ = writeln!(help, "  {:width$}  {}", command.name, command.about);
+    }
+
+    write_args(
+        &mut help,
+        "Global options",
+        &GLOBAL_ARGS.iter().collect::<Vec<_>>(),
+    );
+    help.push_str("\nSee `cargo <command> --help` for the options of a command.\n");
+    help
+}
+
+/// Help for the flags of a solution binary.
+pub fn solution_help(day: &str) -> String {
+    let mut help = format!("Solution of day {day}.\n\nUsage: cargo run --bin {day} -- [options]\n");
+    write_args(
+        &mut help,
+        "Options",
+        &solution_args::ALL.iter().collect::<Vec<_>>(),
+    );
+    help
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Name of the binary that completion scripts are generated for.
+const BIN: &str = "advent_of_code";
+
+/// Generates a completion script for `shell`, one of [`SHELLS`].
+///
+/// Scripts complete both the binary and its cargo aliases, e.g. `cargo solve`. Other cargo
+/// subcommands keep cargo's own completions. Load them with `eval "$(cargo completions bash)"`,
+/// `eval "$(cargo completions zsh)"` or `cargo completions fish | source`.
+pub fn completions(shell: &str) -> Option<String> {
+    match shell {
+        "bash" => Some(bash()),
+        "zsh" => Some(zsh()),
+        "fish" => Some(fish()),
+        _ => None,
+    }
+}
+
+/// Commands that have a cargo alias in `.cargo/config.toml`, e.g. `cargo solve`.
+fn aliased() -> impl Iterator<Item = &'static Command> + Clone {
+    COMMANDS.iter().filter(|c| c.name != "help")
+}
+
+fn alias_names(separator: &str) -> String {
+    aliased()
+        .map(|c| c.name)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn flags(command: &Command) -> impl Iterator<Item = &Arg> {
+    command
+        .args
+        .iter()
+        .chain(GLOBAL_ARGS)
+        .filter(|a| !a.is_positional())
+}
+
+fn bash() -> String {
+    let names: Vec<_> = COMMANDS.iter().map(|c| c.name).collect();
+    let mut s = format!(
+        "_{BIN}() {{\n    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"\n\n    if [[ $COMP_CWORD -eq 1 ]]; then\n        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n        return\n    fi\n\n    case \"${{COMP_WORDS[1]}}\" in\n",
+        names.join(" ")
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(s, "        {})", command.name);
+
+        let with_choices: Vec<_> = flags(command).filter(|a| !a.choices.is_empty()).collect();
+        if !with_choices.is_empty() {
+            s.push_str("            case \"$prev\" in\n");
+            for arg in with_choices {
+                let _ = writeln!(
+                    s,
+                    "                {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;",
+                    arg.name,
+                    arg.choices.join(" ")
+                );
+            }
+            s.push_str("            esac\n");
+        }
+
+        let words: Vec<_> = flags(command)
+            .map(|a| a.name)
+            .chain(
+                command
+                    .args
+                    .iter()
+                    .flat_map(|a| if a.is_positional() { a.choices } else { &[] })
+                    .copied(),
+            )
+            .collect();
+        let _ = writeln!(
+            s,
+            "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;",
+            words.join(" ")
+        );
+    }
+
+    let _ = write!(s, "    esac\n}}\n\ncomplete -F _{BIN} {BIN}\n");
+
+    // cargo aliases put the command at the same position as the binary does.
+    let _ = write!(
+        s,
+        "\n# cargo aliases, other cargo subcommands fall back to cargo's own completion.\n\
+        complete -p cargo &>/dev/null || {{ type _completion_loader &>/dev/null && _completion_loader cargo; }}\n\
+        _{BIN}_cargo_fallback=$(complete -p cargo 2>/dev/null | sed -n 's/.*-F \\([^ ]*\\).*/\\1/p')\n\
+        [[ $_{BIN}_cargo_fallback == _{BIN}_cargo ]] && _{BIN}_cargo_fallback=\n\n\
+        _{BIN}_cargo() {{\n    \
+            if [[ $COMP_CWORD -ge 2 && \" {} \" == *\" ${{COMP_WORDS[1]}} \"* ]]; then\n        \
+                _{BIN}\n    \
+            elif [[ -n $_{BIN}_cargo_fallback ]]; then\n        \
+                \"$_{BIN}_cargo_fallback\" \"$@\"\n    \
+            fi\n\
+        }}\n\n\
+        complete -o default -F _{BIN}_cargo cargo\n",
+        alias_names(" ")
+    );
+    s
+}
+
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn zsh() -> String {
+    let mut s = format!("#compdef {BIN}\n\n_{BIN}() {{\n    local -a commands\n    commands=(\n");
+
+    for command in COMMANDS {
+        let _ = writeln!(
+            s,
+            "        '{}:{}'",
+            command.name,
+            zsh_escape(command.about)
+        );
+    }
+
+    let _ = write!(
+        s,
+        "    )\n\n    if (( CURRENT == 2 )); then\n        _describe 'command' commands\n        return\n    fi\n\n    shift words\n    (( CURRENT-- ))\n\n    case $words[1] in\n"
+    );
+
+    for command in COMMANDS {
+        let mut specs: Vec<String> = flags(command)
+            .map(|arg| {
+                let mut spec = format!("'{}[{}]", arg.name, zsh_escape(arg.help));
+                if let Some(value) = arg.value {
+                    let value = value.trim_matches(['<', '>']);
+                    if value == "path" {
+                        let _ = write!(spec, ":{value}:_files");
+                    } else if arg.choices.is_empty() {
+                        let _ = write!(spec, ":{value}:");
+                    } else {
+                        let _ = write!(spec, ":{value}:({})", arg.choices.join(" "));
+                    }
+                }
+                spec.push('\'');
+                spec
+            })
+            .collect();
+
+        for (i, arg) in command
+            .args
+            .iter()
+            .filter(|a| a.is_positional())
+            .enumerate()
+        {
+            let name = arg.name.trim_matches(['<', '>', '[', ']']);
+            let choices = if arg.choices.is_empty() {
+                " ".to_string()
+            } else {
+                format!("({})", arg.choices.join(" "))
+            };
+            specs.push(format!("'{}:{name}:{choices}'", i + 1));
+        }
+
+        let _ = writeln!(
+            s,
+            "        {}) _arguments {} ;;",
+            command.name,
+            specs.join(" ")
+        );
+    }
+
+    let _ = write!(s, "    esac\n}}\n\ncompdef _{BIN} {BIN}\n");
+
+    // cargo aliases put the command at the same position as the binary does.
+    let _ = write!(
+        s,
+        "\n# cargo aliases, other cargo subcommands fall back to cargo's own completion.\n\
+        _{BIN}_cargo() {{\n    \
+            case $words[2] in\n        \
+                {}) if (( CURRENT > 2 )); then _{BIN}; return; fi ;;\n    \
+            esac\n    \
+            (( $+functions[_cargo] )) && _cargo \"$@\"\n\
+        }}\n\n\
+        compdef _{BIN}_cargo cargo\n",
+        alias_names("|")
+    );
+    s
+}
+
+fn fish() -> String {
+    let mut s = format!("complete -c {BIN} -f\n");
+    fish_commands(&mut s, BIN, COMMANDS.iter());
+
+    // fish merges these with cargo's own completions.
+    s.push_str("\n# cargo aliases\n");
+    fish_commands(&mut s, "cargo", aliased());
+    s
+}
+
+fn fish_commands<'a>(
+    s: &mut String,
+    bin: &str,
+    commands: impl Iterator<Item = &'a Command> + Clone,
+) {
+    for command in commands.clone() {
+        let _ = writeln!(
+            s,
+            "complete -c {bin} -n __fish_use_subcommand -a {} -d '{}'",
+            command.name,
+            command.about.replace('\'', "\\'")
+        );
+    }
+
+    for command in commands {
+        let condition = format!("'__fish_seen_subcommand_from {}'", command.name);
+
+        for arg in flags(command) {
+            let mut line = format!(
+                "complete -c {bin} -n {condition} -l {}",
+                arg.name.trim_start_matches('-')
+            );
+            match arg.value {
+                Some("<path>") => line.push_str(" -r -F"),
+                Some(_) => line.push_str(" -r"),
+                None => (),
+            }
+            if !arg.choices.is_empty() {
+                let _ = write!(line, " -a '{}'", arg.choices.join(" "));
+            }
+            let _ = write!(line, " -d '{}'", arg.help.replace('\'', "\\'"));
+            let _ = writeln!(s, "{line}");
+        }
+
+        for arg in command
+            .args
+            .iter()
+            .filter(|a| a.is_positional() && !a.choices.is_empty())
+        {
+            let _ = writeln!(
+                s,
+                "complete -c {bin} -n {condition} -a '{}'",
+                arg.choices.join(" ")
+            );
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::ffi::OsString;
+
+    use super::{aliased, command_help, completions, find, positionals, suggest, unknown_command};
+
+    #[test]
+    fn suggests_close_names() {
+        let candidates = ["--release", "--dhat", "--submit"];
+        assert_eq!(suggest("--relase", candidates), Some("--release"));
+        assert_eq!(suggest("--sub", candidates), Some("--submit"));
+        assert_eq!(suggest("--verbose", candidates), None);
+        assert!(unknown_command("sovle").contains("Did you mean `solve`?"));
+    }
+
+    #[test]
+    fn rejects_unexpected_arguments() {
+        let solve = find("solve").unwrap();
+        assert_eq!(positionals(solve, vec!["1".into()]).unwrap(), vec!["1"]);
+
+        let err = positionals(solve, vec![OsString::from("--relese")]).unwrap_err();
+        assert!(err.contains("unexpected argument `--relese`. Did you mean `--release`?"));
+        assert!(err.contains(
+            "Usage: cargo solve <day> [--release] [--dhat] [--part <part>] [--submit <part>]"
+        ));
+
+        let err = positionals(solve, vec!["1".into(), "2".into()]).unwrap_err();
+        assert!(err.contains("unexpected argument `2`."));
+    }
+
+    #[test]
+    fn generates_help() {
+        let help = command_help(find("time").unwrap());
+        assert!(help.contains("  [day]  Only benchmark this day."));
+        assert!(help.contains("  --report <format>"));
+        assert!(help.contains("Global options:"));
+    }
+
+    #[test]
+    fn generates_completions() {
+        for shell in ["bash", "zsh", "fish"] {
+            let script = completions(shell).unwrap();
+            assert!(script.contains("crosscheck"));
+            assert!(script.contains("csv json markdown junit"));
+        }
+        assert!(completions("powershell").is_none());
+    }
+
+    #[test]
+    fn completes_cargo_aliases() {
+        let config = std::fs::read_to_string(".cargo/config.toml").unwrap();
+        for command in aliased() {
+            assert!(
+                config.contains(&format!("\n{} = \"run ", command.name)),
+                "`{}` has no cargo alias",
+                command.name
+            );
+        }
+
+        assert!(
+            completions("bash")
+                .unwrap()
+                .contains("complete -o default -F _advent_of_code_cargo cargo")
+        );
+        assert!(
+            completions("zsh")
+                .unwrap()
+                .contains("compdef _advent_of_code_cargo cargo")
+        );
+        assert!(
+            completions("fish")
+                .unwrap()
+                .contains("complete -c cargo -n '__fish_seen_subcommand_from solve' -l release")
+        );
+    }
+}
@@ -1,5 +1,26 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::report::Report;
+use crate::template::{all_days, run_multi::run_multi, Day, Part};
+
+pub fn handle(is_release: bool, part: Option<Part>, report: Option<Report>) {
+    let run = run_multi(&all_days().collect(), is_release, false, part);
+
+    if let Some(report) = report
+        && let Err(e) = report.write(&run.results)
+    {
+        eprintln!("Failed to write report: {e}");
+        process::exit(1);
+    }
+
+    exit_if_failed(&run.failed);
+}
+
+/// Exits with an error if the solution of any day exited unsuccessfully.
+pub fn exit_if_failed(failed: &[Day]) {
+    if !failed.is_empty() {
+        let days: Vec<String> = failed.iter().map(ToString::to_string).collect();
+        eprintln!("Solution(s) of day(s) {} failed.", days.join(", "));
+        process::exit(1);
+    }
 }
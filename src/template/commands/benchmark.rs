@@ -0,0 +1,34 @@
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::template::Day;
+use crate::template::config::Config;
+
+/// Runs the bench target of a day, see [`crate::template::bench`].
+pub fn handle(day: Day, filter: Option<String>, forwarded: &[String]) {
+    let bench_path = format!("benches/{day}.rs");
+    if !Path::new(&bench_path).exists() {
+        eprintln!("Day {day} has no bench target, expected it at \"{bench_path}\".");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec!["bench".to_string(), "--bench".to_string(), day.to_string()];
+
+    let features = &Config::get().solve.features;
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.extend(forwarded.iter().cloned());
+    cmd_args.extend(filter);
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -0,0 +1,10 @@
+use crate::template::config::Config;
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+/// Prints every configuration value together with where it was resolved from.
+pub fn handle() {
+    for (key, value, source) in Config::get().entries() {
+        let value = if value.is_empty() { "-" } else { value };
+        println!("{key} = {value} {ANSI_ITALIC}({source}){ANSI_RESET}");
+    }
+}
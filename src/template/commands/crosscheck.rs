@@ -0,0 +1,39 @@
+use std::process::{Command, Stdio};
+
+use crate::template::cli::solution_args::{CASES, CROSSCHECK, MAX_SIZE, SEED};
+use crate::template::Day;
+
+pub fn handle(day: Day, cases: Option<u64>, seed: Option<u64>, max_size: Option<usize>) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--release".to_string(),
+        "--".to_string(),
+        CROSSCHECK.name.to_string(),
+    ];
+
+    if let Some(cases) = cases {
+        cmd_args.push(CASES.name.to_string());
+        cmd_args.push(cases.to_string());
+    }
+
+    if let Some(seed) = seed {
+        cmd_args.push(SEED.name.to_string());
+        cmd_args.push(seed.to_string());
+    }
+
+    if let Some(max_size) = max_size {
+        cmd_args.push(MAX_SIZE.name.to_string());
+        cmd_args.push(max_size.to_string());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -1,6 +1,11 @@
 pub mod all;
+pub mod benchmark;
+pub mod config;
+pub mod crosscheck;
 pub mod download;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
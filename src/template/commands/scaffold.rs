@@ -1,13 +1,29 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
+use crate::template::config::Config;
 use crate::template::Day;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+const BENCH_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template_bench.txt"));
+const EXAMPLES_TEMPLATE: &str = "\
+# Expected answers of the examples, checked by `cargo test` and `cargo solve %DAY% --examples`.
+# Parts are only checked once their answer is filled in.
+# Every table names an example file in this folder, e.g. `[%DAY%-2]` for `%DAY%-2.txt`.
+[%DAY%]
+# part_one = 0
+# part_two = 0
+";
+
+/// Bench targets are registered in the manifest, since they do not use the default harness.
+static MANIFEST_PATH: &str = "Cargo.toml";
+static BENCHES_DIR: &str = "benches";
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -28,9 +44,11 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let config = Config::get();
+    let input_path = format!("{}/{day}.txt", config.paths.inputs.display());
+    let example_path = format!("{}/{day}.txt", config.paths.examples.display());
+    let manifest_path = format!("{}/{day}.toml", config.paths.examples.display());
+    let module_path = config.bin_path(day);
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -74,6 +92,79 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    // NOTE: never overwrite a manifest, it holds answers that were filled in by hand.
+    match safe_create_file(&manifest_path, false) {
+        Ok(mut file) => {
+            let contents = EXAMPLES_TEMPLATE.replace("%DAY%", &day.to_string());
+            if let Err(e) = file.write_all(contents.as_bytes()) {
+                eprintln!("Failed to write example manifest: {e}");
+                process::exit(1);
+            }
+            println!("Created example manifest \"{}\"", &manifest_path);
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => {
+            eprintln!("Failed to create example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
+    if let Err(e) = create_bench(day, &module_path, overwrite) {
+        eprintln!("Failed to create bench target: {e}");
+        process::exit(1);
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/// Creates the bench target of a day and registers it in `Cargo.toml`.
+fn create_bench(day: Day, module_path: &str, overwrite: bool) -> Result<(), std::io::Error> {
+    let bench_path = format!("{BENCHES_DIR}/{day}.rs");
+
+    let module_path = module_path.trim_start_matches("./");
+    let include_path = if Path::new(module_path).is_absolute() {
+        format!("{module_path:?}")
+    } else {
+        format!("concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/{module_path}\")")
+    };
+
+    fs::create_dir_all(BENCHES_DIR)?;
+    safe_create_file(&bench_path, overwrite)?.write_all(
+        BENCH_TEMPLATE
+            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .replace("%BIN_PATH%", &include_path)
+            .as_bytes(),
+    )?;
+    println!("Created bench file \"{bench_path}\"");
+
+    let manifest = fs::read_to_string(MANIFEST_PATH)?;
+    if let Some(manifest) = register_bench(&manifest, day) {
+        fs::write(MANIFEST_PATH, manifest)?;
+        println!("Registered bench target \"{day}\" in \"{MANIFEST_PATH}\"");
+    }
+
+    Ok(())
+}
+
+/// Adds a `[[bench]]` entry for the day in front of the dependencies.
+/// Returns `None` if the day is registered already.
+fn register_bench(manifest: &str, day: Day) -> Option<String> {
+    let name = format!("name = \"{day}\"");
+    if manifest
+        .split("[[bench]]")
+        .skip(1)
+        .any(|entry| entry.lines().any(|l| l.trim() == name))
+    {
+        return None;
+    }
+
+    let entry = format!("[[bench]]\n{name}\nharness = false\n\n");
+    let position = manifest
+        .find("\n[dependencies]")
+        .map_or(manifest.len(), |p| p + 1);
+
+    let mut manifest = manifest.to_string();
+    manifest.insert_str(position, &entry);
+    Some(manifest)
+}
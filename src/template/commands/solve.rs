@@ -1,28 +1,52 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::cli::solution_args::{EXAMPLES, PART, SUBMIT, VARIANTS};
+use crate::template::config::Config;
+use crate::template::{Day, Part};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    part: Option<Part>,
+    submit_part: Option<Part>,
+    variants: bool,
+    examples: bool,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    let mut features = Config::get().solve.features.clone();
 
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap".to_string());
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if let Some(part) = part {
+        cmd_args.push(PART.name.to_string());
+        cmd_args.push(part.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
+        cmd_args.push(SUBMIT.name.to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if variants {
+        cmd_args.push(VARIANTS.name.to_string());
+    }
+
+    if examples {
+        cmd_args.push(EXAMPLES.name.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
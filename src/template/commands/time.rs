@@ -1,11 +1,36 @@
 use std::collections::HashSet;
+use std::process::{self, Command, Stdio};
 
+use crate::template::cli::solution_args::{PART, SCALE, TIME};
+use crate::template::commands::all::exit_if_failed;
+use crate::template::config::Config;
+use crate::template::report::Report;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Part};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    part: Option<Part>,
+    scales: Option<Vec<usize>>,
+    report: Option<Report>,
+) {
+    if let Some(scales) = scales {
+        let Some(day) = day else {
+            eprintln!("`--scale` requires a day, e.g. `cargo time 8 --scale 1,2,4,8`.");
+            process::exit(1);
+        };
+
+        run_scaled(day, part, &scales);
+        return;
+    }
+
+    let stored_timings = Timings::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read stored timings: {e}");
+        process::exit(1);
+    });
 
     let days_to_run = day.map_or_else(
         || {
@@ -14,27 +39,66 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| match part {
+                        Some(part) => !stored_timings.is_part_complete(*day, part),
+                        None => !stored_timings.is_day_complete(*day),
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(&days_to_run, true, true, part);
+    let timings = run.timings.unwrap();
+
+    if let Some(report) = report
+        && let Err(e) = report.write(&run.results)
+    {
+        eprintln!("Failed to write report: {e}");
+        process::exit(1);
+    }
 
+    // timings of days that failed are not recorded, the others are still stored.
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        store_timings(stored_timings.merge(&timings));
+    }
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+    exit_if_failed(&run.failed);
+}
+
+fn store_timings(merged_timings: Timings) {
+    merged_timings.store_file().unwrap();
+
+    if !Config::get().output.update_readme {
+        println!("Stored updated benchmarks.");
+        return;
+    }
+
+    println!();
+    match readme_benchmarks::update(merged_timings) {
+        Ok(()) => {
+            println!("Stored updated benchmarks.");
+        }
+        Err(_) => {
+            eprintln!("Failed to store updated benchmarks.");
         }
     }
 }
+
+/// Benchmarks a day on generated inputs, see [`crate::template::stress`].
+fn run_scaled(day: Day, part: Option<Part>, scales: &[usize]) {
+    let scales: Vec<String> = scales.iter().map(ToString::to_string).collect();
+    let part_args = part.map(|part| [PART.name.to_string(), part.to_string()]);
+
+    let mut cmd = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &day.to_string()])
+        .args(["--", TIME.name, SCALE.name, &scales.join(",")])
+        .args(part_args.iter().flatten())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
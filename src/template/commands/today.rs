@@ -0,0 +1,61 @@
+use std::process;
+
+use crate::template::commands::scaffold;
+use crate::template::unlock::{self, Clock, SystemClock};
+use crate::template::{Day, aoc_cli};
+
+/// Number of attempts for fetching a freshly unlocked puzzle before giving up.
+const FETCH_ATTEMPTS: u32 = 8;
+
+pub fn handle(wait: bool) {
+    handle_with_clock(&SystemClock, wait);
+}
+
+pub fn handle_with_clock(clock: &impl Clock, wait: bool) {
+    let day = if wait {
+        let year = aoc_cli::get_year().map(i32::from);
+        let Some((day, unlock)) = unlock::next_unlock(clock.now(), year) else {
+            eprintln!("There are no more puzzles to unlock for the configured year.");
+            process::exit(1);
+        };
+
+        unlock::wait_until(clock, day, unlock);
+        day
+    } else {
+        current_day_or_exit(clock)
+    };
+
+    scaffold::handle(day, false);
+
+    if aoc_cli::check().is_err() {
+        eprintln!(
+            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+        );
+        process::exit(1);
+    }
+
+    // puzzles can take a moment to become available after unlocking.
+    if let Err(e) = unlock::retry_with_backoff(clock, FETCH_ATTEMPTS, || aoc_cli::download(day)) {
+        eprintln!("failed to call aoc-cli: {e}");
+        process::exit(1);
+    }
+
+    if let Err(e) = unlock::retry_with_backoff(clock, FETCH_ATTEMPTS, || aoc_cli::read(day)) {
+        eprintln!("failed to call aoc-cli: {e}");
+        process::exit(1);
+    }
+}
+
+fn current_day_or_exit(clock: &impl Clock) -> Day {
+    match unlock::current_day(clock.now()) {
+        Some(day) => day,
+        None => {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the last day of the event in december. Please use `scaffold` with a specific day, \
+                or `today --wait` to wait for the next puzzle."
+            );
+            process::exit(1)
+        }
+    }
+}
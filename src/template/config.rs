@@ -0,0 +1,395 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// Every setting can be overridden with an environment variable named after its key,
+/// e.g. `paths.inputs` is overridden by `AOC_PATHS_INPUTS` and `year` by `AOC_YEAR`.
+/// The main binary additionally accepts `--config <path>` and `--set <key>=<value>`.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::{env, fs, process};
+
+use crate::template::Day;
+
+/// Environment variable that points to an alternative configuration file.
+pub const CONFIG_PATH_ENV: &str = "AOC_CONFIG";
+static DEFAULT_CONFIG_PATH: &str = "aoc.toml";
+
+/// All supported keys with their default values. An empty default means "not set".
+const KEYS: &[(&str, &str)] = &[
+    ("year", ""),
+    ("session_file", ""),
+    ("paths.inputs", "data/inputs"),
+    ("paths.examples", "data/examples"),
+    ("paths.puzzles", "data/puzzles"),
+    ("paths.bins", "./src/bin"),
+    ("paths.timings", "./data/timings.json"),
+    ("paths.answers", "./data/answers.json"),
+    ("paths.readme", "README.md"),
+    ("bench.target_ms", "1000"),
+    ("bench.min_samples", "10"),
+    ("bench.max_samples", "10000"),
+    ("bench.pin_core", ""),
+    ("solve.release", "false"),
+    ("solve.dhat", "false"),
+    ("solve.features", ""),
+    ("output.update_readme", "true"),
+    ("output.report_format", ""),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(String),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "${var}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Paths {
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    pub bins: PathBuf,
+    pub timings: PathBuf,
+    pub answers: PathBuf,
+    pub readme: PathBuf,
+}
+
+#[derive(Clone, Debug)]
+pub struct Bench {
+    pub target_ms: u64,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Core that `time` pins solution binaries to. Only supported on Linux.
+    pub pin_core: Option<usize>,
+}
+
+/// Defaults for flags of the `solve` and `all` commands.
+#[derive(Clone, Debug)]
+pub struct Solve {
+    pub release: bool,
+    pub dhat: bool,
+    /// Cargo features that solution binaries are built with.
+    pub features: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Output {
+    /// Whether `time --store` also updates the benchmark table in the README.
+    pub update_readme: bool,
+    /// Format used for `--out` when `--report` is not passed.
+    pub report_format: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub year: Option<u16>,
+    pub session_file: Option<PathBuf>,
+    pub paths: Paths,
+    pub bench: Bench,
+    pub solve: Solve,
+    pub output: Output,
+    entries: Vec<(&'static str, String, Source)>,
+}
+
+impl Config {
+    /// Returns the configuration of this process. Exits if the configuration is invalid.
+    pub fn get() -> &'static Config {
+        static CONFIG: OnceLock<Config> = OnceLock::new();
+        CONFIG.get_or_init(|| {
+            Config::load().unwrap_or_else(|e| {
+                eprintln!("Invalid configuration: {e}");
+                process::exit(1);
+            })
+        })
+    }
+
+    /// Loads the configuration file (if present) and applies environment overrides.
+    pub fn load() -> Result<Self, String> {
+        let (path, required) = match env::var(CONFIG_PATH_ENV) {
+            Ok(path) => (PathBuf::from(path), true),
+            Err(_) => (PathBuf::from(DEFAULT_CONFIG_PATH), false),
+        };
+
+        let file = match fs::read_to_string(&path) {
+            Ok(contents) => Some((path, contents)),
+            Err(_) if !required => None,
+            Err(e) => return Err(format!("could not read \"{}\": {e}", path.display())),
+        };
+
+        Self::resolve(file, |var| env::var(var).ok())
+    }
+
+    fn resolve(
+        file: Option<(PathBuf, String)>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, String> {
+        let mut values = match &file {
+            Some((path, contents)) => {
+                parse_toml(contents).map_err(|e| format!("{}: {e}", path.display()))?
+            }
+            None => HashMap::new(),
+        };
+
+        if let Some(key) = values
+            .keys()
+            .find(|k| !KEYS.iter().any(|(key, _)| key == k))
+        {
+            return Err(format!("unknown key `{key}`."));
+        }
+
+        let entries: Vec<_> = KEYS
+            .iter()
+            .map(|&(key, default)| {
+                let var = env_var_name(key);
+                if let Some(value) = env(&var) {
+                    (key, value, Source::Env(var))
+                } else if let Some(value) = values.remove(key) {
+                    // NOTE: the file is present whenever it contributed a value.
+                    let path = file.as_ref().map(|f| f.0.clone()).unwrap_or_default();
+                    (key, value, Source::File(path))
+                } else {
+                    (key, default.to_string(), Source::Default)
+                }
+            })
+            .collect();
+
+        let get = |key: &str| -> &str {
+            entries
+                .iter()
+                .find(|(k, ..)| *k == key)
+                .map(|(_, value, _)| value.as_str())
+                .unwrap_or_default()
+        };
+
+        let optional = |key: &str| Some(get(key)).filter(|v| !v.is_empty());
+
+        let bench = Bench {
+            target_ms: parse_value("bench.target_ms", get("bench.target_ms"))?,
+            min_samples: parse_value("bench.min_samples", get("bench.min_samples"))?,
+            max_samples: parse_value("bench.max_samples", get("bench.max_samples"))?,
+            pin_core: optional("bench.pin_core")
+                .map(|v| parse_value("bench.pin_core", v))
+                .transpose()?,
+        };
+
+        if bench.min_samples > bench.max_samples {
+            return Err(format!(
+                "`bench.min_samples` ({}) must not exceed `bench.max_samples` ({}).",
+                bench.min_samples, bench.max_samples
+            ));
+        }
+
+        Ok(Config {
+            year: optional("year")
+                .map(|v| parse_value("year", v))
+                .transpose()?,
+            session_file: optional("session_file").map(expand_home),
+            paths: Paths {
+                inputs: get("paths.inputs").into(),
+                examples: get("paths.examples").into(),
+                puzzles: get("paths.puzzles").into(),
+                bins: get("paths.bins").into(),
+                timings: get("paths.timings").into(),
+                answers: get("paths.answers").into(),
+                readme: get("paths.readme").into(),
+            },
+            bench,
+            solve: Solve {
+                release: parse_value("solve.release", get("solve.release"))?,
+                dhat: parse_value("solve.dhat", get("solve.dhat"))?,
+                features: get("solve.features")
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            },
+            output: Output {
+                update_readme: parse_value("output.update_readme", get("output.update_readme"))?,
+                report_format: optional("output.report_format").map(str::to_string),
+            },
+            entries,
+        })
+    }
+
+    /// Returns the directory for a data folder such as `inputs` or `examples`.
+    pub fn data_dir(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => self.paths.inputs.clone(),
+            "examples" => self.paths.examples.clone(),
+            "puzzles" => self.paths.puzzles.clone(),
+            _ => PathBuf::from("data").join(folder),
+        }
+    }
+
+    /// Path of the solution binary source for a day.
+    pub fn bin_path(&self, day: Day) -> String {
+        format!("{}/{day}.rs", self.paths.bins.display())
+    }
+
+    /// The resolved values with their sources, in declaration order.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str, &Source)> {
+        self.entries.iter().map(|(k, v, s)| (*k, v.as_str(), s))
+    }
+}
+
+/// Returns whether `key` is a supported configuration key.
+pub fn is_known_key(key: &str) -> bool {
+    KEYS.iter().any(|(k, _)| *k == key)
+}
+
+/// Name of the environment variable that overrides `key`, e.g. `AOC_PATHS_INPUTS`.
+pub fn env_var_name(key: &str) -> String {
+    format!("AOC_{}", key.replace('.', "_").to_uppercase())
+}
+
+fn parse_value<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{key}`."))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Parses the subset of TOML used by `aoc.toml`: tables, comments and string, integer
+/// and boolean values. Keys are flattened to `table.key`, values are returned unquoted.
+pub(crate) fn parse_toml(s: &str) -> Result<HashMap<String, String>, String> {
+    let mut values = HashMap::new();
+    let mut table = String::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        let err = |msg: &str| format!("line {}: {msg}", i + 1);
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| err("unclosed table header."))?;
+            table = format!("{}.", name.trim());
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `key = value`."))?;
+
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('"') {
+            quoted
+                .strip_suffix('"')
+                .ok_or_else(|| err("unterminated string."))?
+                .replace("\\\"", "\"")
+                .replace("\\\\", "\\")
+        } else if value == "true" || value == "false" || value.parse::<i64>().is_ok() {
+            value.to_string()
+        } else {
+            return Err(err(&format!("unsupported value `{value}`.")));
+        };
+
+        values.insert(format!("{table}{}", key.trim()), value);
+    }
+
+    Ok(values)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => (),
+        }
+    }
+    line
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Config, Source, parse_toml};
+
+    fn resolve(contents: &str, env: &[(&str, &str)]) -> Result<Config, String> {
+        let file = Some((PathBuf::from("aoc.toml"), contents.to_string()));
+        Config::resolve(file, |var| {
+            env.iter()
+                .find(|(k, _)| *k == var)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn parses_toml_subset() {
+        let values = parse_toml(
+            "year = 2025 # comment\n\n[paths]\ninputs = \"my # inputs\"\n[solve]\nrelease = true\n",
+        )
+        .unwrap();
+        assert_eq!(values["year"], "2025");
+        assert_eq!(values["paths.inputs"], "my # inputs");
+        assert_eq!(values["solve.release"], "true");
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(parse_toml("year 2025").is_err());
+        assert!(parse_toml("[paths").is_err());
+        assert!(parse_toml("inputs = data").is_err());
+    }
+
+    #[test]
+    fn uses_defaults() {
+        let config = resolve("", &[]).unwrap();
+        assert_eq!(config.year, None);
+        assert_eq!(config.paths.inputs, PathBuf::from("data/inputs"));
+        assert_eq!(config.bench.min_samples, 10);
+        assert!(!config.solve.release);
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let config = resolve(
+            "year = 2024\n[bench]\nmin_samples = 5\n",
+            &[("AOC_YEAR", "2025")],
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(2025));
+        assert_eq!(config.bench.min_samples, 5);
+
+        let (_, _, source) = config.entries().find(|(k, ..)| *k == "year").unwrap();
+        assert_eq!(*source, Source::Env("AOC_YEAR".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_invalid_values() {
+        assert!(resolve("[paths]\ninptus = \"x\"\n", &[]).is_err());
+        assert!(resolve("[bench]\nmin_samples = true\n", &[]).is_err());
+    }
+
+    #[test]
+    fn rejects_min_samples_above_max_samples() {
+        assert!(resolve("[bench]\nmin_samples = 20\nmax_samples = 10\n", &[]).is_err());
+        assert!(resolve("", &[("AOC_BENCH_MAX_SAMPLES", "5")]).is_err());
+        assert!(resolve("", &[("AOC_BENCH_MIN_SAMPLES", "10000")]).is_ok());
+    }
+}
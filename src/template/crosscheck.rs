@@ -0,0 +1,271 @@
+/// Property-based cross-checking of solution parts against reference implementations.
+/// Random inputs are produced by the day's generator, and every registered part is compared to its reference.
+use std::any::Any;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::template::generator::{Generator, Rng};
+use crate::template::params;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Part};
+
+/// Number of seeds tried per size while shrinking a disagreeing case.
+const SHRINK_ATTEMPTS: u64 = 100;
+
+/// A type-erased part implementation that renders its answer as a string.
+pub type AnswerFn = Box<dyn Fn(&str) -> Option<String>>;
+
+/// The outcome of running a part on an input: either an answer or a panic message.
+pub type Outcome = Result<Option<String>, String>;
+
+/// A solution part together with the reference implementation it must agree with.
+pub struct Check {
+    pub part: Part,
+    pub solution: AnswerFn,
+    pub reference: AnswerFn,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    pub cases: u64,
+    pub seed: u64,
+    pub max_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            cases: 1000,
+            seed: 2025,
+            max_size: 20,
+        }
+    }
+}
+
+/// A generated input on which a part and its reference produce different outcomes.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub part: Part,
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub solution: Outcome,
+    pub reference: Outcome,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{ANSI_BOLD}Part {} disagrees with its reference{ANSI_RESET} (seed: {}, size: {})",
+            self.part, self.seed, self.size
+        )?;
+        writeln!(f, "Solution:  {}", format_outcome(&self.solution))?;
+        writeln!(f, "Reference: {}", format_outcome(&self.reference))?;
+        writeln!(f, "Input:")?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Why a cross-check failed.
+#[derive(Debug)]
+pub enum Failure {
+    Disagreement(Disagreement),
+    /// The generator panicked, so there is no input to check.
+    Generator {
+        seed: u64,
+        size: usize,
+        message: String,
+    },
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Disagreement(disagreement) => disagreement.fmt(f),
+            Failure::Generator {
+                seed,
+                size,
+                message,
+            } => write!(
+                f,
+                "{ANSI_BOLD}The generator panicked{ANSI_RESET} (seed: {seed}, size: {size}): {message}"
+            ),
+        }
+    }
+}
+
+fn format_outcome(outcome: &Outcome) -> String {
+    match outcome {
+        Ok(Some(answer)) => answer.clone(),
+        Ok(None) => "✖".into(),
+        Err(message) => format!("panicked: {message}"),
+    }
+}
+
+/// Runs `options.cases` generated inputs through every check, with sizes growing from 1 to `options.max_size`.
+/// Returns the number of cases that were checked, or the smallest disagreement found after the first failure.
+pub fn run(generator: Generator, checks: &[Check], options: &Options) -> Result<u64, Failure> {
+    // generated inputs have no `.params` file, parts must not see those of an earlier read.
+    params::clear();
+
+    // panics are reported as outcomes, silence the default hook while the checks run.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = (0..options.cases).try_for_each(|n| {
+        let seed = Rng::derive_seed(options.seed, n);
+        let size = 1 + (n as usize * options.max_size) / options.cases.max(1) as usize;
+
+        match check_case(generator, checks, seed, size) {
+            Err(Failure::Disagreement(disagreement)) => {
+                Err(shrink(generator, checks, disagreement))
+            }
+            result => result,
+        }
+    });
+
+    panic::set_hook(hook);
+    result.map(|()| options.cases)
+}
+
+/// Looks for a failure at a smaller size than `found`, preferring the smallest size.
+fn shrink(generator: Generator, checks: &[Check], found: Disagreement) -> Failure {
+    for size in 1..found.size {
+        for attempt in 0..SHRINK_ATTEMPTS {
+            let seed = Rng::derive_seed(found.seed, attempt);
+            if let Err(smaller) = check_case(generator, checks, seed, size) {
+                return smaller;
+            }
+        }
+    }
+
+    Failure::Disagreement(found)
+}
+
+fn check_case(
+    generator: Generator,
+    checks: &[Check],
+    seed: u64,
+    size: usize,
+) -> Result<(), Failure> {
+    let input = panic::catch_unwind(|| generator(&mut Rng::new(seed), size)).map_err(|err| {
+        Failure::Generator {
+            seed,
+            size,
+            message: panic_message(err.as_ref()),
+        }
+    })?;
+
+    let disagreement = checks.iter().find_map(|check| {
+        let solution = outcome(&check.solution, &input);
+        let reference = outcome(&check.reference, &input);
+
+        (solution != reference).then(|| Disagreement {
+            part: check.part,
+            seed,
+            size,
+            input: input.clone(),
+            solution,
+            reference,
+        })
+    });
+
+    disagreement.map_or(Ok(()), |d| Err(Failure::Disagreement(d)))
+}
+
+fn outcome(func: &AnswerFn, input: &str) -> Outcome {
+    panic::catch_unwind(AssertUnwindSafe(|| func(input))).map_err(|err| panic_message(err.as_ref()))
+}
+
+fn panic_message(err: &(dyn Any + Send)) -> String {
+    err.downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| err.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Check, Failure, Options, run};
+    use crate::template::Part;
+    use crate::template::generator::Rng;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| rng.range(0..100).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn sum(input: &str) -> Option<String> {
+        Some(
+            input
+                .lines()
+                .map(|l| l.parse::<u64>().unwrap())
+                .sum::<u64>()
+                .to_string(),
+        )
+    }
+
+    fn broken_sum(input: &str) -> Option<String> {
+        // drops the last line once inputs get longer than three lines.
+        let lines: Vec<_> = input.lines().collect();
+        let take = if lines.len() > 3 {
+            lines.len() - 1
+        } else {
+            lines.len()
+        };
+        Some(
+            lines[..take]
+                .iter()
+                .map(|l| l.parse::<u64>().unwrap())
+                .sum::<u64>()
+                .to_string(),
+        )
+    }
+
+    #[test]
+    fn passes_agreeing_implementations() {
+        let checks = [Check {
+            part: Part::One,
+            solution: Box::new(sum),
+            reference: Box::new(sum),
+        }];
+        assert_eq!(run(generate, &checks, &Options::default()).unwrap(), 1000);
+    }
+
+    #[test]
+    fn shrinks_disagreements() {
+        let checks = [Check {
+            part: Part::One,
+            solution: Box::new(broken_sum),
+            reference: Box::new(sum),
+        }];
+        let Failure::Disagreement(disagreement) =
+            run(generate, &checks, &Options::default()).unwrap_err()
+        else {
+            panic!("expected a disagreement");
+        };
+        assert_eq!(disagreement.part, Part::One);
+        assert_eq!(disagreement.size, 4);
+    }
+
+    #[test]
+    fn reports_generator_panics() {
+        fn panicking(_: &mut Rng, size: usize) -> String {
+            assert!(size < 3, "size too large");
+            "1".into()
+        }
+
+        let checks = [Check {
+            part: Part::One,
+            solution: Box::new(sum),
+            reference: Box::new(sum),
+        }];
+        let failure = run(panicking, &checks, &Options::default()).unwrap_err();
+        assert!(matches!(
+            failure,
+            Failure::Generator { size: 3, ref message, .. } if message == "size too large"
+        ));
+    }
+}
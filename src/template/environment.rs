@@ -0,0 +1,201 @@
+/// Describes the machine and build that benchmarks run on, and detects conditions that make
+/// timings unreliable.
+use std::{env, fs, process::Command, thread, time::Duration};
+
+/// The build and machine that produced a timing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Environment {
+    /// Cargo profile of the benchmarked binary, `release` or `dev`.
+    pub profile: String,
+    pub machine: String,
+    /// `rustc --version` when the benchmark ran. Solutions are built right before they are
+    /// timed, so this is the toolchain that built them unless it was switched in between.
+    pub toolchain: String,
+    /// CPU model name, if it could be detected.
+    pub cpu: Option<String>,
+    /// Number of logical cores available to the benchmark.
+    pub cores: Option<usize>,
+    /// Target features that `rustc` enables with the `RUSTFLAGS` of the benchmark run, e.g.
+    /// `sse2` or `avx2`. Flags from cargo's own config, e.g. `build.rustflags`, are not included.
+    pub target_features: Vec<String>,
+    /// Cargo features the binary was compiled with.
+    pub features: Vec<String>,
+    /// Core the benchmark was pinned to, if any.
+    pub pinned_core: Option<usize>,
+}
+
+impl Environment {
+    /// Describes the current machine and the toolchain on its `PATH` at runtime.
+    pub fn capture(is_release: bool, features: &[String], pinned_core: Option<usize>) -> Self {
+        let hostname = command_output("hostname", &[]).unwrap_or_else(|| "unknown".into());
+
+        Environment {
+            profile: if is_release { "release" } else { "dev" }.into(),
+            machine: format!("{hostname} ({}-{})", env::consts::OS, env::consts::ARCH),
+            toolchain: command_output("rustc", &["--version"]).unwrap_or_else(|| "unknown".into()),
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().map(usize::from).ok(),
+            target_features: target_features(),
+            features: features.to_vec(),
+            pinned_core,
+        }
+    }
+
+    /// A one-line description, as shown below the benchmark table.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![];
+
+        let cores = self.cores.map(|n| match n {
+            1 => "1 core".to_string(),
+            n => format!("{n} cores"),
+        });
+
+        match (&self.cpu, cores) {
+            (Some(cpu), Some(cores)) => parts.push(format!("{cpu} ({cores})")),
+            (Some(cpu), None) => parts.push(cpu.clone()),
+            (None, Some(cores)) => parts.push(cores),
+            (None, None) => parts.push(self.machine.clone()),
+        }
+
+        if let Some(core) = self.pinned_core {
+            parts.push(format!("pinned to core {core}"));
+        }
+
+        parts.push(format!("runtime toolchain {}", self.toolchain));
+        parts.push(format!("`{}` profile", self.profile));
+
+        if !self.target_features.is_empty() {
+            parts.push(format!(
+                "runtime target features: `{}`",
+                self.target_features.join(",")
+            ));
+        }
+
+        if !self.features.is_empty() {
+            parts.push(format!("features: `{}`", self.features.join(",")));
+        }
+
+        parts.join(", ")
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn cpu_model() -> Option<String> {
+    match env::consts::OS {
+        "linux" => fs::read_to_string("/proc/cpuinfo")
+            .ok()?
+            .lines()
+            .find(|l| l.starts_with("model name"))
+            .and_then(|l| l.split_once(':'))
+            .map(|(_, model)| model.trim().to_string()),
+        "macos" => command_output("sysctl", &["-n", "machdep.cpu.brand_string"]),
+        "windows" => env::var("PROCESSOR_IDENTIFIER").ok(),
+        _ => None,
+    }
+}
+
+/// Target features that `rustc` enables with the current `RUSTFLAGS`. This is what the solutions
+/// are built with by `cargo all`/`cargo time`, not necessarily by an earlier, separate build.
+fn target_features() -> Vec<String> {
+    let rustflags = env::var("RUSTFLAGS").unwrap_or_default();
+    let mut args = vec!["--print", "cfg"];
+    args.extend(rustflags.split_whitespace());
+
+    command_output("rustc", &args)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.strip_prefix("target_feature=\""))
+        .filter_map(|l| l.strip_suffix('"'))
+        .map(str::to_string)
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Checks whether solution binaries can be pinned to a core, which requires `taskset` on Linux.
+pub fn check_pinning() -> Result<(), String> {
+    if env::consts::OS != "linux" {
+        return Err("pinning is only supported on Linux.".into());
+    }
+
+    Command::new("taskset")
+        .arg("--version")
+        .output()
+        .map(|_| ())
+        .map_err(|_| "`taskset` is not present in environment.".into())
+}
+
+/// Returns warnings about conditions that make timings unreliable.
+/// Detection is only supported on Linux, other platforms never produce warnings.
+pub fn check_noise() -> Vec<String> {
+    if env::consts::OS != "linux" {
+        return vec![];
+    }
+
+    let mut warnings = vec![];
+
+    let governors: Vec<String> = fs::read_dir("/sys/devices/system/cpu")
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| fs::read_to_string(entry.path().join("cpufreq/scaling_governor")).ok())
+        .map(|governor| governor.trim().to_string())
+        .filter(|governor| governor != "performance")
+        .collect();
+
+    if let Some(governor) = governors.first() {
+        warnings.push(format!(
+            "CPU frequency scaling is active (governor `{governor}`), consider switching to `performance`."
+        ));
+    }
+
+    if fs::read_to_string("/sys/devices/system/cpu/cpufreq/boost").is_ok_and(|b| b.trim() == "1") {
+        warnings.push("CPU boost is enabled, clock speeds may vary between runs.".into());
+    }
+
+    if let Some(busy) = busy_cores()
+        && busy >= 1.0
+    {
+        warnings.push(format!(
+            "The system is busy ({busy:.1} cores in use by other processes)."
+        ));
+    }
+
+    warnings
+}
+
+/// Number of cores that are busy, sampled from `/proc/stat` over a short interval.
+fn busy_cores() -> Option<f64> {
+    let sample = || -> Option<(u64, u64)> {
+        let stat = fs::read_to_string("/proc/stat").ok()?;
+        let values: Vec<u64> = stat
+            .lines()
+            .next()?
+            .split_whitespace()
+            .skip(1)
+            .filter_map(|v| v.parse().ok())
+            .collect();
+        // idle and iowait
+        let idle = values.get(3)? + values.get(4).unwrap_or(&0);
+        Some((values.iter().sum(), idle))
+    };
+
+    let (total_start, idle_start) = sample()?;
+    thread::sleep(Duration::from_millis(200));
+    let (total_end, idle_end) = sample()?;
+
+    let total = total_end.checked_sub(total_start).filter(|t| *t > 0)? as f64;
+    let idle = idle_end.saturating_sub(idle_start) as f64;
+    let cores = thread::available_parallelism().map_or(1, usize::from) as f64;
+
+    Some((1.0 - idle / total) * cores)
+}
@@ -0,0 +1,233 @@
+/// Example manifests list the example files of a day with the answers they are expected to produce.
+///
+/// The manifest of a day lives next to its examples, e.g. `data/examples/11.toml`. Every table
+/// names an example file (without `.txt`) and the expected answer of each part it applies to:
+///
+/// ```toml
+/// [11-1]
+/// part_one = 5
+///
+/// [11]
+/// part_two = 2
+/// ```
+///
+/// Examples are checked by the test that `solution!` generates and by `cargo solve <day> --examples`.
+/// Only answers that are filled in are checked, so a freshly scaffolded manifest passes. The test
+/// also passes for days without a manifest, while `--examples` reports it as an error.
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::template::config::{Config, parse_toml};
+use crate::template::crosscheck::AnswerFn;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Part, params};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Name of the example file, without extension.
+    pub name: String,
+    pub expected: Vec<(Part, String)>,
+}
+
+/// The outcome of running a part on an example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub example: String,
+    pub part: Part,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        self.actual.as_ref() == Some(&self.expected)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "example `{}`, part {}: expected `{}`, got `{}`",
+            self.example,
+            self.part,
+            self.expected,
+            self.actual.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+pub fn manifest_path(day: Day) -> PathBuf {
+    Config::get()
+        .data_dir("examples")
+        .join(format!("{day}.toml"))
+}
+
+/// Reads the manifest of a day. Returns `Ok(None)` if the day has no manifest.
+pub fn read_manifest(day: Day) -> Result<Option<Vec<Example>>, String> {
+    let path = manifest_path(day);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+
+    parse_manifest(&contents)
+        .map(Some)
+        .map_err(|e| format!("\"{}\": {e}", path.display()))
+}
+
+/// Parses a manifest, examples are sorted by name.
+pub fn parse_manifest(s: &str) -> Result<Vec<Example>, String> {
+    let mut examples: Vec<Example> = vec![];
+
+    for (key, value) in parse_toml(s)? {
+        let Some((name, field)) = key.rsplit_once('.') else {
+            return Err(format!(
+                "`{key}` does not belong to an example, expected it in a table like `[{key}]`."
+            ));
+        };
+
+        let part = match field {
+            "part_one" => Part::One,
+            "part_two" => Part::Two,
+            _ => {
+                return Err(format!(
+                    "unknown key `{field}` in `[{name}]`, expected `part_one` or `part_two`."
+                ));
+            }
+        };
+
+        match examples.iter_mut().find(|e| e.name == name) {
+            Some(example) => example.expected.push((part, value)),
+            None => examples.push(Example {
+                name: name.to_string(),
+                expected: vec![(part, value)],
+            }),
+        }
+    }
+
+    examples.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    for example in &mut examples {
+        example.expected.sort_unstable();
+    }
+
+    Ok(examples)
+}
+
+/// Runs every part on the examples it applies to. Parts without an implementation or without an
+/// expected answer are skipped. Like [`crate::template::read_file_part`], an example loads the
+/// parameters of `<name>.params`, or of `<day>.params` if that does not exist.
+pub fn run(
+    day: Day,
+    examples: &[Example],
+    parts: &[(Part, &AnswerFn)],
+) -> Result<Vec<Outcome>, String> {
+    let dir = Config::get().data_dir("examples");
+    let read = |name: &str| {
+        let path = dir.join(format!("{name}.txt"));
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("could not read \"{}\": {e}", path.display()))?;
+        params::load_or(
+            &dir.join(format!("{name}.params")),
+            &dir.join(format!("{day}.params")),
+        );
+        Ok::<_, String>(input)
+    };
+    let mut outcomes = vec![];
+
+    for example in examples {
+        let input = read(&example.name)?;
+
+        for (part, expected) in &example.expected {
+            let Some((_, answer)) = parts.iter().find(|(p, _)| p == part) else {
+                continue;
+            };
+
+            outcomes.push(Outcome {
+                example: example.name.clone(),
+                part: *part,
+                expected: expected.clone(),
+                actual: answer(&input),
+            });
+        }
+    }
+
+    Ok(outcomes)
+}
+
+/// Prints expected against actual answers.
+pub fn print(outcomes: &[Outcome]) {
+    let width = outcomes
+        .iter()
+        .map(|o| o.example.len())
+        .max()
+        .unwrap_or(0)
+        .max("Example".len());
+
+    println!(
+        "{ANSI_BOLD}{:width$}  Part  Expected → Actual{ANSI_RESET}",
+        "Example"
+    );
+    for outcome in outcomes {
+        let status = if outcome.is_ok() { "✔" } else { "✖" };
+        println!(
+            "{:width$}  {:4}  {} → {} {status}",
+            outcome.example,
+            outcome.part.to_string(),
+            outcome.expected,
+            outcome.actual.as_deref().unwrap_or("-")
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, Outcome, parse_manifest};
+    use crate::template::Part;
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = "[11]\npart_two = 2\n\n[11-1]\npart_one = 5\n";
+        assert_eq!(
+            parse_manifest(manifest).unwrap(),
+            vec![
+                Example {
+                    name: "11".into(),
+                    expected: vec![(Part::Two, "2".into())],
+                },
+                Example {
+                    name: "11-1".into(),
+                    expected: vec![(Part::One, "5".into())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_string_answers() {
+        let manifest = "[10]\npart_one = \"ABC\"\npart_two = 7\n";
+        let examples = parse_manifest(manifest).unwrap();
+        assert_eq!(
+            examples[0].expected,
+            vec![(Part::One, "ABC".into()), (Part::Two, "7".into())]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        assert!(parse_manifest("part_one = 5").is_err());
+        assert!(parse_manifest("[01]\npart_three = 5").is_err());
+    }
+
+    #[test]
+    fn compares_answers() {
+        let outcome = |actual: Option<&str>| Outcome {
+            example: "01".into(),
+            part: Part::One,
+            expected: "5".into(),
+            actual: actual.map(str::to_string),
+        };
+        assert!(outcome(Some("5")).is_ok());
+        assert!(!outcome(Some("6")).is_ok());
+        assert!(!outcome(None).is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+/// Seeded, deterministic input generation for solutions.
+/// Generators are plain functions so that they can be registered through the `solution!` macro.
+use std::ops::Range;
+
+/// Builds a puzzle input of (roughly) `size` elements. What an element is depends on the puzzle.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// A small, seedable pseudo-random number generator (`SplitMix64`).
+///
+/// Not suitable for anything but generating test inputs; the output sequence for a given
+/// seed is stable across platforms and releases.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next random [`u64`].
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random value in the half-open `range`. Panics if the range is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "cannot sample from an empty range");
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// Returns a random index into a collection of length `len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(0..denominator) < numerator
+    }
+
+    /// Returns a random element of `items`. Panics if `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Derives an independent seed for the `n`-th case of a run seeded with `seed`.
+    pub fn derive_seed(seed: u64, n: u64) -> u64 {
+        Rng::new(seed ^ n.wrapping_mul(0xD1B5_4A32_D192_ED03)).next_u64()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn stays_within_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            let value = rng.range(10..20);
+            assert!((10..20).contains(&value));
+        }
+    }
+}
@@ -1,13 +1,31 @@
 use std::{env, fs};
 
+use config::Config;
+
 pub mod aoc_cli;
+pub mod bench;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod crosscheck;
+pub mod examples;
+pub mod generator;
+pub mod params;
 pub mod runner;
+pub mod solution;
+pub mod stress;
+#[cfg(feature = "today")]
+pub mod unlock;
+pub mod variants;
 
 pub use day::*;
+pub use part::*;
 
 mod day;
+mod environment;
+mod part;
 mod readme_benchmarks;
+pub mod report;
 mod run_multi;
 mod timings;
 
@@ -16,42 +34,53 @@ pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Also loads the puzzle parameters of the file, see [`params`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let dir = cwd.join(Config::get().data_dir(folder));
+    params::load(&dir.join(format!("{day}.params")));
+    let f = fs::read_to_string(dir.join(format!("{day}.txt")));
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Parameters are loaded from `01-2.params`, or `01.params` if that does not exist.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: Day, part: Part) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+    let dir = cwd.join(Config::get().data_dir(folder));
+    params::load_or(
+        &dir.join(format!("{day}-{part}.params")),
+        &dir.join(format!("{day}.params")),
+    );
+    let f = fs::read_to_string(dir.join(format!("{day}-{part}.txt")));
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also generates a test that checks the parts against the day's example manifest.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Additional hooks can be registered as `key = value` pairs, where each key is a method of
+/// [`solution::Solution`], e.g. `solution!(3, generator = generate_input, reference_one = reference_part_one)`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, One] [part_two, Two]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, One]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, Two]);
+    };
+    ($day:expr, $( $key:ident = $value:expr ),+ $(,)?) => {
+        $crate::solution!(@impl $day, [part_one, One] [part_two, Two] $( ; $key = $value )+);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$func:expr, $part:ident] )* $( ; $key:ident = $value:expr )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +88,23 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        fn solution() -> $crate::template::solution::Solution {
+            $crate::template::solution::Solution::new(DAY)
+                $( .part($crate::template::Part::$part, $func) )*
+                $( .$key($value) )*
+        }
+
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            solution().run();
+        }
+
+        /// Checks every part against the example manifest of the day, see [`$crate::template::examples`].
+        #[cfg(test)]
+        mod solution_examples {
+            #[test]
+            fn examples() {
+                super::solution().test_examples();
+            }
         }
     };
 }
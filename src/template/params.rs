@@ -0,0 +1,111 @@
+/// Puzzle parameters that differ between the example and the real input, e.g. a number of
+/// steps or the size of a grid.
+///
+/// Parts read a parameter with [`param`], passing the value for the real input as default.
+/// Other values are set in a `.params` file next to the input, e.g. `data/examples/08.params`:
+///
+/// ```toml
+/// pairs = 10
+/// ```
+///
+/// [`crate::template::read_file`] loads the parameters of the file it reads, so tests and the
+/// runner pass the right values without further setup.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::template::config::parse_toml;
+
+thread_local! {
+    // NOTE: thread-local, since tests read their inputs and run their parts on separate threads.
+    static PARAMS: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Returns the parameter `key` of the input that was read last on this thread, or `default`.
+/// Panics if the parameter cannot be parsed as `T`.
+///
+/// Parameters are global to the thread: they are replaced whenever an input is read with
+/// [`crate::template::read_file`] or [`crate::template::read_file_part`], and cleared before
+/// generated inputs are checked or stress-tested. Inputs that are built in code, e.g. string
+/// literals in tests, see the parameters of the last file read on the same thread.
+pub fn param<T: FromStr>(key: &str, default: T) -> T {
+    PARAMS.with_borrow(|params| match params.get(key) {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid value `{value}` for puzzle parameter `{key}`")),
+        None => default,
+    })
+}
+
+/// Replaces the parameters of this thread with those in the file at `path`. A missing file
+/// clears them, so that all parameters take their default values.
+pub(crate) fn load(path: &Path) {
+    let params = match fs::read_to_string(path) {
+        Ok(contents) => parse_toml(&contents)
+            .unwrap_or_else(|e| panic!("could not parse \"{}\": {e}", path.display())),
+        Err(_) => HashMap::new(),
+    };
+
+    set(params);
+}
+
+/// Like [`load`], but reads `fallback` if there is no file at `path`.
+pub(crate) fn load_or(path: &Path, fallback: &Path) {
+    load(if path.exists() { path } else { fallback });
+}
+
+/// Replaces the parameters of this thread.
+pub fn set(params: HashMap<String, String>) {
+    PARAMS.set(params);
+}
+
+/// Resets all parameters of this thread to their default values.
+pub(crate) fn clear() {
+    set(HashMap::new());
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use super::{load, param, set};
+
+    #[test]
+    fn falls_back_to_defaults() {
+        set(HashMap::new());
+        assert_eq!(param("pairs", 1000), 1000);
+    }
+
+    #[test]
+    fn reads_typed_parameters() {
+        set(HashMap::from([
+            ("pairs".to_string(), "10".to_string()),
+            ("name".to_string(), "example".to_string()),
+        ]));
+        assert_eq!(param("pairs", 1000_usize), 10);
+        assert_eq!(param("name", String::new()), "example");
+        assert!(param("missing", true));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_invalid_values() {
+        set(HashMap::from([("pairs".to_string(), "ten".to_string())]));
+        param("pairs", 1000_usize);
+    }
+
+    #[test]
+    fn clears_stale_parameters() {
+        let path = std::env::temp_dir().join("advent_of_code_params_test.params");
+        fs::write(&path, "pairs = 10\n").unwrap();
+        load(&path);
+        assert_eq!(param("pairs", 1000), 10);
+
+        fs::remove_file(&path).unwrap();
+        load(&path);
+        assert_eq!(param("pairs", 1000), 1000);
+    }
+}
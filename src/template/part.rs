@@ -0,0 +1,218 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// One of the two parts of a puzzle.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::template::Part;
+/// let part: Part = "2".parse().unwrap();
+/// assert_eq!(part, Part::Two);
+/// assert_eq!(part.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Creates a [`Part`] from its number, returns [`None`] for anything but 1 or 2.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub fn into_inner(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number of 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The first year of Advent of Code.
+const FIRST_YEAR: u16 = 2015;
+
+/// Uniquely identifies a puzzle part across all events.
+///
+/// # Display
+/// This value displays as `year/day/part`, which is also the format accepted by [`FromStr`].
+///
+/// ```
+/// # use advent_of_code::template::{Part, PuzzleId};
+/// # use advent_of_code::day;
+/// let id = PuzzleId::new(2025, day!(8), Part::One).unwrap();
+/// assert_eq!(id.to_string(), "2025/08/1");
+/// assert_eq!("2025/08/1".parse::<PuzzleId>().unwrap(), id);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: u16,
+    pub day: Day,
+    pub part: Part,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`] if the day exists in the event of the given year.
+    pub fn new(year: u16, day: Day, part: Part) -> Result<Self, PuzzleIdError> {
+        if year < FIRST_YEAR {
+            return Err(PuzzleIdError::InvalidYear(year));
+        }
+
+        if day.into_inner() > days_in_event(year) {
+            return Err(PuzzleIdError::InvalidDay(year, day));
+        }
+
+        Ok(Self { year, day, part })
+    }
+}
+
+/// Number of puzzles in the event of `year`. Since 2025, events have 12 puzzles instead of 25.
+pub fn days_in_event(year: u16) -> u8 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}/{}", self.year, self.day, self.part)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = s.split('/');
+
+        let (Some(year), Some(day), Some(part), None) = (
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+        ) else {
+            return Err(PuzzleIdError::Format(s.to_string()));
+        };
+
+        let year = year
+            .parse()
+            .map_err(|_| PuzzleIdError::Format(s.to_string()))?;
+        let day = day
+            .parse()
+            .map_err(|_| PuzzleIdError::Format(s.to_string()))?;
+        let part = part
+            .parse()
+            .map_err(|_| PuzzleIdError::Format(s.to_string()))?;
+
+        Self::new(year, day, part)
+    }
+}
+
+/// An error which can be returned when creating or parsing a [`PuzzleId`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum PuzzleIdError {
+    Format(String),
+    InvalidYear(u16),
+    InvalidDay(u16, Day),
+}
+
+impl Error for PuzzleIdError {}
+
+impl Display for PuzzleIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleIdError::Format(s) => {
+                write!(f, "expecting a puzzle as `year/day/part`, got `{s}`")
+            }
+            PuzzleIdError::InvalidYear(year) => {
+                write!(
+                    f,
+                    "there is no event in {year}, the first one was in {FIRST_YEAR}"
+                )
+            }
+            PuzzleIdError::InvalidDay(year, day) => write!(
+                f,
+                "day {day} does not exist in {year}, the event has {} days",
+                days_in_event(*year)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Part, PuzzleId, PuzzleIdError};
+    use crate::day;
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("3".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn parses_puzzle_ids() {
+        let id: PuzzleId = "2024/25/2".parse().unwrap();
+        assert_eq!(id.year, 2024);
+        assert_eq!(id.day, day!(25));
+        assert_eq!(id.part, Part::Two);
+        assert_eq!(id.to_string(), "2024/25/2");
+    }
+
+    #[test]
+    fn validates_puzzle_ids() {
+        assert_eq!(
+            PuzzleId::new(2014, day!(1), Part::One),
+            Err(PuzzleIdError::InvalidYear(2014))
+        );
+        assert_eq!(
+            PuzzleId::new(2025, day!(13), Part::One),
+            Err(PuzzleIdError::InvalidDay(2025, day!(13)))
+        );
+        assert!("2025/08".parse::<PuzzleId>().is_err());
+        assert!("2025/08/1/2".parse::<PuzzleId>().is_err());
+    }
+}
@@ -2,8 +2,9 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::config::Config;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Day, Part};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -27,7 +28,7 @@ pub struct TablePosition {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    Config::get().bin_path(day)
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -53,6 +54,7 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 }
 
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+    let environments = describe_environments(&timings);
     let header = format!("{prefix} Benchmarks");
 
     let mut lines: Vec<String> = vec![
@@ -69,18 +71,57 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.part(Part::One).map_or("-", |p| &p.display),
+            timing.part(Part::Two).map_or("-", |p| &p.display)
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if !environments.is_empty() {
+        lines.push(String::new());
+        lines.extend(environments);
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
+/// Lists the environments the timings were measured in, with the days they apply to
+/// if there is more than one.
+fn describe_environments(timings: &Timings) -> Vec<String> {
+    let mut environments: Vec<(String, Vec<String>)> = vec![];
+
+    for timing in &timings.data {
+        let Some(environment) = &timing.environment else {
+            continue;
+        };
+
+        let summary = environment.summary();
+        let day = timing.day.into_inner().to_string();
+        match environments.iter_mut().find(|(s, _)| *s == summary) {
+            Some((_, days)) => days.push(day),
+            None => environments.push((summary, vec![day])),
+        }
+    }
+
+    match environments.as_slice() {
+        [] => vec![],
+        [(summary, _)] => vec![format!("_Measured on {summary}._")],
+        _ => {
+            let mut lines = vec!["_Measured on:_".to_string(), String::new()];
+            lines.extend(
+                environments
+                    .iter()
+                    .map(|(summary, days)| format!("- Day {}: {summary}", days.join(", "))),
+            );
+            lines
+        }
+    }
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -89,7 +130,7 @@ fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+    let path = &Config::get().paths.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
     update_content(&mut readme, timings, total_millis)?;
@@ -100,28 +141,38 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::timings::Environment, template::timings::PartTiming,
+        template::timings::Timing, template::timings::Timings,
+    };
+
+    fn part(display: &str) -> Option<PartTiming> {
+        PartTiming::from_display(display, Some(10))
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: part("10ms"),
+                    part_2: part("20ms"),
                     total_nanos: 3e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: part("30ms"),
+                    part_2: part("40ms"),
                     total_nanos: 7e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: part("40ms"),
+                    part_2: part("50ms"),
                     total_nanos: 9e+10,
+                    environment: None,
                 },
             ],
         }
@@ -180,4 +231,32 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn lists_environments() {
+        let environment = |cpu: &str| Environment {
+            profile: "release".into(),
+            machine: "box (linux-x86_64)".into(),
+            toolchain: "rustc 1.95.0".into(),
+            cpu: Some(cpu.into()),
+            cores: Some(8),
+            target_features: vec!["sse2".into()],
+            features: vec![],
+            pinned_core: None,
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].environment = Some(environment("CPU A"));
+        timings.data[1].environment = Some(environment("CPU A"));
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings.clone(), 190.0).unwrap();
+        assert!(s.contains(
+            "_Measured on CPU A (8 cores), runtime toolchain rustc 1.95.0, `release` profile, runtime target features: `sse2`._"
+        ));
+
+        timings.data[2].environment = Some(environment("CPU B"));
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("- Day 1, 2: CPU A (8 cores)"));
+        assert!(s.contains("- Day 4: CPU B (8 cores)"));
+    }
 }
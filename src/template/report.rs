@@ -0,0 +1,354 @@
+/// Module that exports the results of `all` and `time` runs for other tools.
+/// Supported formats are CSV, JSON, a standalone Markdown table and JUnit XML.
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{fs, io};
+
+use tinyjson::JsonValue;
+
+use crate::template::config::Config;
+use crate::template::run_multi::PartResult;
+use crate::template::{Day, Part};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    Markdown,
+    Junit,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "junit" | "xml" => Ok(Self::Junit),
+            _ => Err(format!(
+                "unknown report format `{s}`, expecting one of: csv, json, markdown, junit."
+            )),
+        }
+    }
+}
+
+/// A report requested on the command-line. Without `out`, the report is printed to stdout.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub format: ReportFormat,
+    pub out: Option<PathBuf>,
+}
+
+impl Report {
+    pub fn write(&self, results: &[PartResult]) -> Result<(), io::Error> {
+        let contents = match self.format {
+            ReportFormat::Csv => to_csv(results),
+            ReportFormat::Json => to_json(results),
+            ReportFormat::Markdown => to_markdown(results),
+            ReportFormat::Junit => to_junit(results, &read_answers()?),
+        };
+
+        match &self.out {
+            Some(path) => {
+                fs::write(path, contents)?;
+                println!("Wrote report to \"{}\".", path.display());
+            }
+            None => print!("{contents}"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Expected answers, keyed by day and part.
+type Answers = HashMap<(Day, Part), String>;
+
+/// Reads expected answers from `paths.answers` (`data/answers.json`), which is maintained by hand.
+/// Fails if the file is missing or invalid, since a JUnit report without answers cannot pass or fail.
+///
+/// The file has the same shape as `timings.json`:
+/// `{ "data": [{ "day": "01", "part_1": "1234", "part_2": null }] }`.
+/// Answers are strings, exactly as the solution prints them: JSON numbers are parsed as `f64`,
+/// which loses precision above 2^53, so they make the file invalid.
+fn read_answers() -> Result<Answers, io::Error> {
+    let path = &Config::get().paths.answers;
+    let contents = fs::read_to_string(path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "JUnit reports compare against the expected answers in \"{}\": {e}",
+                path.display()
+            ),
+        )
+    })?;
+
+    parse_answers(&contents).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("\"{}\" is not a valid answers file.", path.display()),
+        )
+    })
+}
+
+fn parse_answers(s: &str) -> Option<Answers> {
+    let json = JsonValue::from_str(s).ok()?;
+    let data = json
+        .get::<HashMap<String, JsonValue>>()?
+        .get("data")?
+        .get::<Vec<JsonValue>>()?;
+
+    let mut answers = Answers::new();
+
+    for entry in data {
+        let entry = entry.get::<HashMap<String, JsonValue>>()?;
+        let day = Day::from_str(entry.get("day")?.get::<String>()?).ok()?;
+
+        for (part, key) in [(Part::One, "part_1"), (Part::Two, "part_2")] {
+            let answer = match entry.get(key) {
+                Some(JsonValue::String(s)) => s.clone(),
+                Some(JsonValue::Null) | None => continue,
+                Some(_) => return None,
+            };
+            answers.insert((day, part), answer);
+        }
+    }
+
+    Some(answers)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn to_csv(results: &[PartResult]) -> String {
+    let mut csv = String::from("day,part,answer,duration,nanos\n");
+
+    for r in results {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{}",
+            r.day,
+            r.part,
+            csv_escape(r.answer.as_deref().unwrap_or_default()),
+            r.duration.as_deref().unwrap_or_default(),
+            r.nanos.map(|n| n.to_string()).unwrap_or_default()
+        );
+    }
+
+    csv
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn to_json(results: &[PartResult]) -> String {
+    let data = results
+        .iter()
+        .map(|r| {
+            let optional = |v: Option<JsonValue>| v.unwrap_or(JsonValue::Null);
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(r.day.to_string()));
+            map.insert("part".into(), JsonValue::Number(f64::from(r.part.into_inner())));
+            map.insert(
+                "answer".into(),
+                optional(r.answer.clone().map(JsonValue::String)),
+            );
+            map.insert(
+                "duration".into(),
+                optional(r.duration.clone().map(JsonValue::String)),
+            );
+            map.insert("nanos".into(), optional(r.nanos.map(JsonValue::Number)));
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("data".into(), JsonValue::Array(data));
+    map.insert("total_nanos".into(), JsonValue::Number(total_nanos(results)));
+
+    // NOTE: formatting a JSON value only fails for non-finite numbers.
+    let json = JsonValue::Object(map).format().unwrap_or_default();
+    format!("{json}\n")
+}
+
+fn to_markdown(results: &[PartResult]) -> String {
+    let mut lines: Vec<String> = vec![
+        "| Day | Part | Answer | Time |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for r in results {
+        lines.push(format!(
+            "| {} | {} | `{}` | `{}` |",
+            r.day.into_inner(),
+            r.part,
+            r.answer.as_deref().map_or_else(|| "-".into(), markdown_escape),
+            markdown_escape(r.duration.as_deref().unwrap_or("-")),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Total: {:.2}ms**",
+        total_nanos(results) / 1_000_000_f64
+    ));
+    lines.push(String::new());
+
+    lines.join("\n")
+}
+
+/// Escapes a table cell, pipes would otherwise end the cell.
+fn markdown_escape(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn to_junit(results: &[PartResult], answers: &Answers) -> String {
+    let mut cases = String::new();
+    let mut failures = 0;
+    let mut skipped = 0;
+
+    for r in results {
+        let seconds = r.nanos.unwrap_or_default() / 1_000_000_000_f64;
+        let _ = write!(
+            cases,
+            "    <testcase classname=\"Day {}\" name=\"Part {}\" time=\"{seconds:.9}\"",
+            r.day, r.part
+        );
+
+        match (&r.answer, answers.get(&(r.day, r.part))) {
+            (None, _) => {
+                failures += 1;
+                cases.push_str(">\n      <failure message=\"no answer\"/>\n    </testcase>\n");
+            }
+            (Some(actual), Some(expected)) if actual != expected => {
+                failures += 1;
+                let message = format!("expected {expected}, got {actual}");
+                let _ = write!(
+                    cases,
+                    ">\n      <failure message=\"{}\"/>\n    </testcase>\n",
+                    xml_escape(&message)
+                );
+            }
+            (Some(_), None) => {
+                skipped += 1;
+                cases.push_str(
+                    ">\n      <skipped message=\"no stored answer\"/>\n    </testcase>\n",
+                );
+            }
+            (Some(_), Some(_)) => cases.push_str("/>\n"),
+        }
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <testsuites>\n  \
+        <testsuite name=\"advent_of_code\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{:.9}\">\n\
+        {cases}  \
+        </testsuite>\n\
+        </testsuites>\n",
+        results.len(),
+        total_nanos(results) / 1_000_000_000_f64,
+    )
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+fn total_nanos(results: &[PartResult]) -> f64 {
+    results.iter().filter_map(|r| r.nanos).sum()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answers, to_csv, to_junit, to_markdown, ReportFormat};
+    use crate::{day, template::run_multi::PartResult, template::Part};
+
+    fn get_mock_results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                day: day!(1),
+                part: Part::One,
+                answer: Some("42".into()),
+                duration: Some("1.0ms".into()),
+                nanos: Some(1_000_000_f64),
+            },
+            PartResult {
+                day: day!(1),
+                part: Part::Two,
+                answer: Some("a,b".into()),
+                duration: Some("2.0ms".into()),
+                nanos: Some(2_000_000_f64),
+            },
+            PartResult {
+                day: day!(2),
+                part: Part::One,
+                answer: None,
+                duration: None,
+                nanos: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("md".parse::<ReportFormat>().unwrap(), ReportFormat::Markdown);
+        assert!("yaml".parse::<ReportFormat>().is_err());
+    }
+
+    #[test]
+    fn writes_csv() {
+        let csv = to_csv(&get_mock_results());
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "day,part,answer,duration,nanos");
+        assert_eq!(lines[1], "01,1,42,1.0ms,1000000");
+        assert_eq!(lines[2], "01,2,\"a,b\",2.0ms,2000000");
+        assert_eq!(lines[3], "02,1,,,");
+    }
+
+    #[test]
+    fn writes_junit() {
+        let answers = parse_answers(
+            r#"{ "data": [{ "day": "01", "part_1": "42", "part_2": "c" }] }"#,
+        )
+        .unwrap();
+        let xml = to_junit(&get_mock_results(), &answers);
+        assert!(xml.contains("tests=\"3\" failures=\"2\" skipped=\"0\""));
+        assert!(xml.contains("expected c, got a,b"));
+    }
+
+    #[test]
+    fn writes_markdown() {
+        let mut results = get_mock_results();
+        results[1].answer = Some("a|b".into());
+        let markdown = to_markdown(&results);
+        let lines: Vec<_> = markdown.lines().collect();
+        assert_eq!(lines[2], "| 1 | 1 | `42` | `1.0ms` |");
+        assert_eq!(lines[3], "| 1 | 2 | `a\\|b` | `2.0ms` |");
+        assert_eq!(lines[4], "| 2 | 1 | `-` | `-` |");
+    }
+
+    #[test]
+    fn keeps_answers_as_strings() {
+        let answers = parse_answers(
+            r#"{ "data": [{ "day": "01", "part_1": "9007199254740993", "part_2": null }] }"#,
+        )
+        .unwrap();
+        assert_eq!(answers[&(day!(1), Part::One)], "9007199254740993");
+        assert!(!answers.contains_key(&(day!(1), Part::Two)));
+
+        // numbers lose precision as `f64`, so they are rejected.
+        assert!(parse_answers(r#"{ "data": [{ "day": "01", "part_1": 42 }] }"#).is_none());
+    }
+}
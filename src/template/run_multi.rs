@@ -1,15 +1,83 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io,
+    path::PathBuf,
+    process,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::config::Config;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Part};
 
 use super::{
-    all_days,
-    timings::{Timing, Timings},
+    all_days, environment,
+    timings::{Environment, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// The outcome of a single part, as printed by a solution binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: Part,
+    pub answer: Option<String>,
+    pub duration: Option<String>,
+    pub nanos: Option<f64>,
+}
+
+/// Results of running a set of days. `timings` is only present for timed runs.
+#[derive(Clone, Debug)]
+pub struct MultiRun {
+    pub timings: Option<Timings>,
+    pub results: Vec<PartResult>,
+    /// Days whose solution failed to compile or exited unsuccessfully, e.g. because it panicked.
+    pub failed: Vec<Day>,
+}
+
+/// Runs the solutions of `days_to_run`. With `part`, only that part of each day is run.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    part: Option<Part>,
+) -> MultiRun {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut results: Vec<PartResult> = vec![];
+
+    let config = Config::get();
+    let features = &config.solve.features;
+    let pin_core = is_timed
+        .then_some(config.bench.pin_core)
+        .flatten()
+        .filter(|_| match environment::check_pinning() {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Warning: not pinning benchmarks, {e}");
+                false
+            }
+        });
+
+    let build = child_commands::build_solutions(days_to_run, is_release, features)
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to build solutions: {e}");
+            process::exit(1);
+        });
+    for error in &build.shared_errors {
+        eprint!("{error}");
+    }
+    // without any executable, errors of shared targets such as the library failed every day.
+    if build.executables.is_empty() && !build.shared_errors.is_empty() {
+        eprintln!("Failed to build solutions.");
+        process::exit(1);
+    }
 
+    let environment = is_timed.then(|| Environment::capture(is_release, features, pin_core));
+    if is_timed {
+        for warning in environment::check_noise() {
+            eprintln!("Warning: {warning}");
+        }
+    }
+
+    let mut failed: Vec<Day> = vec![];
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
@@ -24,17 +92,42 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let (output, status) = if let Some(executable) = build.executables.get(&day) {
+                let (output, status) =
+                    child_commands::run_solution(executable, is_timed, part, pin_core)
+                        .unwrap_or_else(|e| {
+                            eprintln!("Failed to run day {day}: {e}");
+                            process::exit(1);
+                        });
+                (output, Some(status))
+            } else if let Some(errors) = build.errors.get(&day) {
+                errors.iter().for_each(|error| eprint!("{error}"));
+                println!("Compile error.");
+                failed.push(day);
+                results.extend(with_missing_parts(vec![], day, part));
+                return;
+            } else {
+                (vec![], None)
+            };
+
+            if let Some(status) = status.filter(|s| !s.success()) {
+                println!("Failed: exited with {status}.");
+                failed.push(day);
 
-            if output.is_empty() {
+                // timings of failed days are discarded.
+                let day_results = child_commands::parse_part_results(&output, day);
+                results.extend(with_missing_parts(day_results, day, part));
+            } else if output.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let mut val = child_commands::parse_exec_time(&output, day);
+                val.environment.clone_from(&environment);
                 timings.push(val);
+                results.extend(child_commands::parse_part_results(&output, day));
             }
         });
 
-    if is_timed {
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,63 +136,221 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        results,
+        failed,
     }
 }
 
-#[allow(dead_code)]
+/// Adds a result without an answer for every part of a failed day that did not finish.
+fn with_missing_parts(
+    mut results: Vec<PartResult>,
+    day: Day,
+    part: Option<Part>,
+) -> Vec<PartResult> {
+    for part in part.map_or(Part::ALL.to_vec(), |part| vec![part]) {
+        if !results.iter().any(|r| r.part == part) {
+            results.push(PartResult {
+                day,
+                part,
+                answer: None,
+                duration: None,
+                nanos: None,
+            });
+        }
+    }
+    results.sort_unstable_by_key(|r| r.part);
+    results
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture the output of the solution."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
+/// Executables of the solution binaries, built once before any of them is run.
+#[derive(Debug, Default)]
+pub struct Build {
+    pub executables: HashMap<Day, PathBuf>,
+    /// Rendered compiler errors of days that failed to compile.
+    pub errors: HashMap<Day, Vec<String>>,
+    /// Rendered compiler errors of targets shared by all days, e.g. the library.
+    pub shared_errors: Vec<String>,
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    Config::get().bin_path(day)
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Build, Error, PartResult, all_days, get_path_for_bin};
+    use crate::template::cli::solution_args;
+    use crate::template::timings::{PartTiming, parse_duration};
+    use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Part};
     use std::{
+        collections::{HashMap, HashSet},
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        path::{Path, PathBuf},
+        process::{Command, ExitStatus, Stdio},
+        str::FromStr,
         thread,
     };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    use tinyjson::JsonValue;
+
+    /// Builds the solution bins of all scaffolded days in `days` with a single cargo invocation.
+    /// Days that fail to compile do not prevent the others from being built.
+    pub fn build_solutions(
+        days: &HashSet<Day>,
+        is_release: bool,
+        features: &[String],
+    ) -> Result<Build, Error> {
+        // skip days that have not been scaffolded yet.
+        let days: Vec<Day> = all_days()
+            .filter(|day| days.contains(day) && Path::new(&get_path_for_bin(*day)).exists())
+            .collect();
+
+        if days.is_empty() {
+            return Ok(Build::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec![
+            "build".to_string(),
+            "--message-format=json".into(),
+            "--keep-going".into(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
+        }
+
+        if !features.is_empty() {
+            args.push("--features".into());
+            args.push(features.join(","));
+        }
+
+        for day in &days {
+            args.push("--bin".into());
+            args.push(day.to_string());
         }
 
+        println!("Compiling {} solution(s)...", days.len());
+
+        // cargo reports progress and failures on stderr, the messages on stdout carry the details.
+        let output = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::null())
+            .output()?;
+
+        let mut build = parse_build_messages(&String::from_utf8_lossy(&output.stdout));
+        build.executables.retain(|day, _| days.contains(day));
+
+        if !output.status.success() && build.errors.is_empty() && build.shared_errors.is_empty() {
+            build
+                .shared_errors
+                .push(format!("cargo exited with {}.\n", output.status));
+        }
+
+        Ok(build)
+    }
+
+    /// Collects built executables and compiler errors from cargo's JSON messages.
+    fn parse_build_messages(messages: &str) -> Build {
+        let mut build = Build::default();
+
+        for message in messages.lines().filter_map(|l| JsonValue::from_str(l).ok()) {
+            let Some(message) = message.get::<HashMap<String, JsonValue>>() else {
+                continue;
+            };
+            let string = |value: Option<&JsonValue>| value?.get::<String>().cloned();
+            let target = message
+                .get("target")
+                .and_then(|t| string(t.get::<HashMap<String, JsonValue>>()?.get("name")));
+            let day = target.as_deref().and_then(|t| t.parse::<Day>().ok());
+
+            match string(message.get("reason")).as_deref() {
+                Some("compiler-artifact") => {
+                    if let (Some(day), Some(executable)) = (day, string(message.get("executable")))
+                    {
+                        build.executables.insert(day, PathBuf::from(executable));
+                    }
+                }
+                Some("compiler-message") => {
+                    let Some(diagnostic) = message
+                        .get("message")
+                        .and_then(|m| m.get::<HashMap<String, JsonValue>>())
+                    else {
+                        continue;
+                    };
+                    if string(diagnostic.get("level")).as_deref() != Some("error") {
+                        continue;
+                    }
+                    let rendered = string(diagnostic.get("rendered")).unwrap_or_default();
+                    match day {
+                        Some(day) => build.errors.entry(day).or_default().push(rendered),
+                        None => build.shared_errors.push(rendered),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        build
+    }
+
+    /// Run a built solution bin, optionally pinned to a single core.
+    pub fn run_solution(
+        executable: &Path,
+        is_timed: bool,
+        part: Option<Part>,
+        pin_core: Option<usize>,
+    ) -> Result<(Vec<String>, ExitStatus), Error> {
+        let mut args: Vec<String> = vec![];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push(solution_args::TIME.name.into());
+        }
+
+        if let Some(part) = part {
+            args.push(solution_args::PART.name.into());
+            args.push(part.to_string());
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = match pin_core {
+            Some(core) => {
+                let mut cmd = Command::new("taskset");
+                cmd.args(["--cpu-list", &core.to_string()]).arg(executable);
+                cmd
+            }
+            None => Command::new(executable),
+        };
+
+        let mut cmd = cmd
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -123,9 +374,9 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok((output, status))
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -134,6 +385,7 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            environment: None,
         };
 
         output
@@ -143,50 +395,79 @@ pub mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(timing) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                let part = l.split(':').next()?.trim().strip_prefix("Part ")?;
+                Some((part.parse::<Part>().ok()?, timing))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+            .for_each(|(part, timing)| {
+                timings.total_nanos += timing.nanos;
+                timings.set_part(part, Some(timing));
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Extracts the answer and duration of every part from the output of a solution.
+    pub fn parse_part_results(output: &[String], day: Day) -> Vec<PartResult> {
+        // intermediate results are overwritten with `\r`, only keep what ends up on screen.
+        let lines: Vec<&str> = output
+            .iter()
+            .map(|l| l.rsplit('\r').next().unwrap_or(l))
+            .collect();
+
+        lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let (part, rest) = line.strip_prefix("Part ")?.split_once(": ")?;
+                let part = part.parse().ok()?;
+
+                let (answer, rest) = if let Some(rest) = rest.strip_prefix(ANSI_BOLD) {
+                    let (answer, rest) = rest.split_once(ANSI_RESET)?;
+                    (Some(answer.to_string()), rest)
+                } else if let Some(rest) = rest.strip_prefix('▼') {
+                    // multi-line answers are printed below the part line.
+                    let answer: Vec<&str> = lines[i + 1..]
+                        .iter()
+                        .take_while(|l| !l.starts_with("Part "))
+                        .copied()
+                        .collect();
+                    (Some(answer.join("\n").trim_end().to_string()), rest)
+                } else {
+                    (None, rest)
+                };
+
+                let duration = rest
+                    .trim()
+                    .strip_prefix('(')
+                    .and_then(|s| s.strip_suffix(')'))
+                    .and_then(|s| s.split('@').next())
+                    .map(str::trim);
+
+                Some(PartResult {
+                    day,
+                    part,
+                    answer,
+                    duration: duration.map(str::to_string),
+                    nanos: duration.and_then(parse_duration),
+                })
+            })
+            .collect()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
+    fn parse_time(line: &str) -> Option<PartTiming> {
+        let (str_timing, str_samples) = line
             .split(" samples)")
             .next()?
             .split('(')
             .next_back()?
-            .split('@')
-            .next()?
-            .trim();
+            .split_once('@')?;
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        PartTiming::from_display(str_timing.trim(), str_samples.trim().parse().ok())
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +486,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_build_messages, parse_exec_time, parse_part_results};
 
         use crate::day;
 
@@ -220,8 +501,11 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let part_1 = res.part_1.unwrap();
+            assert_eq!(part_1.display, "74.13ns");
+            assert_approx_eq!(part_1.nanos, 74.13_f64);
+            assert_eq!(part_1.samples, Some(100000));
+            assert_eq!(res.part_2.unwrap().display, "74.13ms");
         }
 
         #[test]
@@ -235,8 +519,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().display, "2s");
+            assert_eq!(res.part_2.unwrap().display, "100ms");
         }
 
         #[test]
@@ -253,5 +537,51 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_part_results() {
+            let res = parse_part_results(
+                &[
+                    "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.5ms @ 10 samples)"
+                        .into(),
+                    "Part 2: ▼ \rPart 2: ▼  (2.0µs)".into(),
+                    "#.#".into(),
+                    ".#.".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].answer.as_deref(), Some("42"));
+            assert_eq!(res[0].duration.as_deref(), Some("1.5ms"));
+            assert_approx_eq!(res[0].nanos.unwrap(), 1_500_000_f64);
+            assert_eq!(res[1].answer.as_deref(), Some("#.#\n.#."));
+            assert_approx_eq!(res[1].nanos.unwrap(), 2000_f64);
+        }
+
+        #[test]
+        fn parses_build_messages() {
+            let build = parse_build_messages(concat!(
+                r#"{"reason":"compiler-artifact","target":{"name":"01"},"executable":"/t/01"}"#,
+                "\n",
+                r#"{"reason":"compiler-message","target":{"name":"02"},"#,
+                r#""message":{"level":"error","rendered":"error[E0425]\n"}}"#,
+                "\n",
+                r#"{"reason":"compiler-message","target":{"name":"02"},"#,
+                r#""message":{"level":"warning","rendered":"warning\n"}}"#,
+                "\n",
+                r#"{"reason":"build-finished","success":false}"#,
+            ));
+            assert_eq!(build.executables[&day!(1)].to_str(), Some("/t/01"));
+            assert_eq!(build.errors[&day!(2)], vec!["error[E0425]\n".to_string()]);
+            assert!(build.shared_errors.is_empty());
+        }
+
+        #[test]
+        fn parses_unsolved_parts() {
+            let res = parse_part_results(&["Part 1: ✖\rPart 1: ✖             ".into()], day!(1));
+            assert_eq!(res[0].answer, None);
+            assert_eq!(res[0].duration, None);
+        }
     }
 }
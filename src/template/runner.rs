@@ -4,30 +4,62 @@ use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process};
 
+use crate::template::config::Config;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, Part, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+/// Flags of a solution binary that change how parts are run, see [`crate::template::cli::solution_args`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench every part (`--time`).
+    pub time: bool,
+    /// Submit the answer of this part (`--submit`).
+    pub submit: Option<Part>,
+}
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Runs, times and prints a part. If the part is selected for submission, its answer is submitted afterwards.
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: Part,
+    options: RunOptions,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(func, input, options.time, |result| {
+        print_result(result, &part_str, "");
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(result) = result
+        && options.submit == Some(part)
+    {
+        // NOTE: without a year, aoc-cli picks the event itself and the day cannot be validated.
+        if let Some(year) = aoc_cli::get_year()
+            && let Err(e) = PuzzleId::new(year, day, part)
+        {
+            eprintln!("Cannot submit: {e}.");
+            process::exit(1);
+        }
+
+        if let Err(e) = submit_result(result, day, part) {
+            eprintln!("Failed to submit part {part} of day {day}: {e}");
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     Both limits can be changed in the `[bench]` section of `aoc.toml`.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
@@ -41,7 +73,7 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_timed {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -50,14 +82,25 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
+/// Bench a solution part without printing its result, e.g. to compare timings across inputs.
+pub fn measure<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> (Duration, u128) {
+    let timer = Instant::now();
+    black_box(func(black_box(input)));
+    let base_time = timer.elapsed();
+
+    bench(func, input, &base_time)
+}
+
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let config = &Config::get().bench;
+    let bench_iterations = (Duration::from_millis(config.target_ms).as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(config.min_samples, config.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -125,41 +168,17 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit one part of the solution if aoc-cli is installed.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
-
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
-
-    if part_submit != part {
-        return None;
-    }
-
+    part: Part,
+) -> Result<Output, aoc_cli::AocCommandError> {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    aoc_cli::submit(day, part, &result.to_string())
 }
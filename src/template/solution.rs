@@ -0,0 +1,380 @@
+/// The runtime description of a day, assembled by the `solution!` macro.
+/// Parts and optional hooks are registered here and dispatched from the generated `main`.
+use std::fmt::Display;
+use std::process;
+
+use pico_args::Arguments;
+
+use crate::template::cli::{self, solution_args};
+use crate::template::crosscheck::{self, AnswerFn, Check};
+use crate::template::examples::{self, Outcome};
+use crate::template::generator::Generator;
+use crate::template::runner::{RunOptions, measure, run_part};
+use crate::template::stress::{self, MeasureFn};
+use crate::template::variants::{self, DEFAULT_VARIANT, Variant};
+use crate::template::{Day, Part, read_file};
+
+/// A type-erased part implementation that runs, times, prints and optionally submits a part.
+type RunFn = Box<dyn Fn(&str, Day, RunOptions)>;
+
+struct RegisteredPart {
+    part: Part,
+    run: RunFn,
+    answer: AnswerFn,
+    measure: MeasureFn,
+}
+
+pub struct Solution {
+    day: Day,
+    parts: Vec<RegisteredPart>,
+    generator: Option<Generator>,
+    base_size: usize,
+    references: Vec<(Part, AnswerFn)>,
+    variants: Vec<(Part, Variant)>,
+}
+
+impl Solution {
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            parts: vec![],
+            generator: None,
+            base_size: 1000,
+            references: vec![],
+            variants: vec![],
+        }
+    }
+
+    /// Registers the implementation of a part.
+    #[must_use]
+    pub fn part<T: Display + 'static>(
+        mut self,
+        part: Part,
+        func: impl Fn(&str) -> Option<T> + Copy + 'static,
+    ) -> Self {
+        self.parts.push(RegisteredPart {
+            part,
+            run: Box::new(move |input, day, options| run_part(func, input, day, part, options)),
+            answer: erase(func),
+            measure: Box::new(move |input| measure(func, input)),
+        });
+        self
+    }
+
+    /// Registers a generator that produces random, valid puzzle inputs.
+    #[must_use]
+    pub fn generator(mut self, generator: Generator) -> Self {
+        self.generator = Some(generator);
+        self
+    }
+
+    /// Sets the size that is passed to the generator at scale 1. Defaults to 1000.
+    /// Ideally, this yields an input that is comparable to the real puzzle input.
+    #[must_use]
+    pub fn base_size(mut self, base_size: usize) -> Self {
+        self.base_size = base_size;
+        self
+    }
+
+    /// Registers a reference implementation that part one is cross-checked against.
+    #[must_use]
+    pub fn reference_one<T: Display + 'static>(
+        mut self,
+        func: impl Fn(&str) -> Option<T> + Copy + 'static,
+    ) -> Self {
+        self.references.push((Part::One, erase(func)));
+        self
+    }
+
+    /// Registers a reference implementation that part two is cross-checked against.
+    #[must_use]
+    pub fn reference_two<T: Display + 'static>(
+        mut self,
+        func: impl Fn(&str) -> Option<T> + Copy + 'static,
+    ) -> Self {
+        self.references.push((Part::Two, erase(func)));
+        self
+    }
+
+    /// Registers a named alternative implementation of part one, e.g.
+    /// `variant_one = ("generic", part_one_generic)`. Variants are compared with `--variants`.
+    #[must_use]
+    pub fn variant_one<T: Display + 'static>(
+        self,
+        variant: (&'static str, impl Fn(&str) -> Option<T> + Copy + 'static),
+    ) -> Self {
+        self.variant(Part::One, variant)
+    }
+
+    /// Registers a named alternative implementation of part two, see [`Solution::variant_one`].
+    #[must_use]
+    pub fn variant_two<T: Display + 'static>(
+        self,
+        variant: (&'static str, impl Fn(&str) -> Option<T> + Copy + 'static),
+    ) -> Self {
+        self.variant(Part::Two, variant)
+    }
+
+    fn variant<T: Display + 'static>(
+        mut self,
+        part: Part,
+        (name, func): (&'static str, impl Fn(&str) -> Option<T> + Copy + 'static),
+    ) -> Self {
+        self.variants.push((
+            part,
+            Variant {
+                name,
+                answer: erase(func),
+                measure: Box::new(move |input| measure(func, input)),
+            },
+        ));
+        self
+    }
+
+    /// Entry point of the generated `main`.
+    pub fn run(mut self) {
+        let mut args = Arguments::from_env();
+
+        if args.contains(["-h", "--help"]) {
+            print!("{}", cli::solution_help(&self.day.to_string()));
+            return;
+        }
+
+        let defaults = crosscheck::Options::default();
+        let crosscheck = args.contains(solution_args::CROSSCHECK.name);
+        let compare_variants = args.contains(solution_args::VARIANTS.name);
+        let check_examples = args.contains(solution_args::EXAMPLES.name);
+        let options = crosscheck::Options {
+            cases: parse_arg(args.opt_value_from_str(solution_args::CASES.name))
+                .unwrap_or(defaults.cases),
+            seed: parse_arg(args.opt_value_from_str(solution_args::SEED.name))
+                .unwrap_or(defaults.seed),
+            max_size: parse_arg(args.opt_value_from_str(solution_args::MAX_SIZE.name))
+                .unwrap_or(defaults.max_size),
+        };
+        let part = parse_arg(args.opt_value_from_str(solution_args::PART.name));
+        let scales =
+            parse_arg(args.opt_value_from_fn(solution_args::SCALE.name, stress::parse_scales));
+        let run_options = RunOptions {
+            time: args.contains(solution_args::TIME.name),
+            submit: parse_arg(args.opt_value_from_str(solution_args::SUBMIT.name)),
+        };
+
+        if let Some(arg) = args.finish().first() {
+            let arg = arg.to_string_lossy();
+            let mut message = format!("Unexpected command-line input: `{arg}`.");
+            let names = solution_args::ALL.iter().map(|a| a.name);
+            if let Some(suggestion) = cli::suggest(&arg, names) {
+                message.push_str(&format!(" Did you mean `{suggestion}`?"));
+            }
+            eprintln!("{message}");
+            process::exit(1);
+        }
+
+        if let Some(part) = part {
+            if !self.parts.iter().any(|p| p.part == part) {
+                eprintln!("Day {} does not implement part {part}.", self.day);
+                process::exit(1);
+            }
+            self.parts.retain(|p| p.part == part);
+            self.references.retain(|(p, _)| *p == part);
+            self.variants.retain(|(p, _)| *p == part);
+        }
+
+        if crosscheck {
+            self.crosscheck(&options);
+            return;
+        }
+
+        if compare_variants {
+            self.compare_variants();
+            return;
+        }
+
+        if check_examples {
+            self.check_examples();
+            return;
+        }
+
+        if let Some(scales) = scales {
+            self.stress(&scales, options.seed);
+            return;
+        }
+
+        let input = read_file("inputs", self.day);
+        for part in &self.parts {
+            (part.run)(&input, self.day, run_options);
+        }
+    }
+
+    fn crosscheck(self, options: &crosscheck::Options) {
+        let generator = self.require_generator();
+
+        let mut answers = self.parts;
+        let checks: Vec<Check> = self
+            .references
+            .into_iter()
+            .filter_map(|(part, reference)| {
+                let idx = answers.iter().position(|p| p.part == part)?;
+                Some(Check {
+                    part,
+                    solution: answers.swap_remove(idx).answer,
+                    reference,
+                })
+            })
+            .collect();
+
+        if checks.is_empty() {
+            eprintln!(
+                "Day {} does not register any reference implementations.",
+                self.day
+            );
+            process::exit(1);
+        }
+
+        match crosscheck::run(generator, &checks, options) {
+            Ok(cases) => {
+                let parts: Vec<_> = checks.iter().map(|c| c.part.to_string()).collect();
+                println!(
+                    "Part(s) {} agree with their reference on {cases} cases (seed: {}).",
+                    parts.join(", "),
+                    options.seed
+                );
+            }
+            Err(failure) => {
+                println!("{failure}");
+                process::exit(1);
+            }
+        }
+    }
+
+    fn compare_variants(self) {
+        if self.variants.is_empty() {
+            eprintln!("Day {} does not register any variants.", self.day);
+            process::exit(1);
+        }
+
+        let orphans: Vec<_> = self
+            .variants
+            .iter()
+            .filter(|(part, _)| !self.parts.iter().any(|p| p.part == *part))
+            .map(|(part, variant)| format!("`{}` (part {part})", variant.name))
+            .collect();
+        if !orphans.is_empty() {
+            eprintln!(
+                "Day {} registers variants of parts it does not implement: {}.",
+                self.day,
+                orphans.join(", ")
+            );
+            process::exit(1);
+        }
+
+        let input = read_file("inputs", self.day);
+        let mut registered = self.variants;
+        let mut agree = true;
+
+        for part in self.parts {
+            let mut variants = vec![Variant {
+                name: DEFAULT_VARIANT,
+                answer: part.answer,
+                measure: part.measure,
+            }];
+
+            // NOTE: keep the registration order of variants.
+            let (own, rest) = registered.into_iter().partition(|(p, _)| *p == part.part);
+            registered = rest;
+            variants.extend(own.into_iter().map(|(_, variant)| variant));
+
+            if variants.len() > 1 {
+                agree &= variants::compare(part.part, &variants, &input);
+            }
+        }
+
+        if !agree {
+            process::exit(1);
+        }
+    }
+
+    fn check_examples(&self) {
+        let outcomes = match self.run_examples() {
+            Ok(Some(outcomes)) => outcomes,
+            Ok(None) => {
+                eprintln!(
+                    "Day {} has no example manifest, create \"{}\".",
+                    self.day,
+                    examples::manifest_path(self.day).display()
+                );
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        if outcomes.is_empty() {
+            println!(
+                "No expected answers yet, fill in `part_one` or `part_two` in \"{}\".",
+                examples::manifest_path(self.day).display()
+            );
+            return;
+        }
+
+        examples::print(&outcomes);
+        if !outcomes.iter().all(Outcome::is_ok) {
+            process::exit(1);
+        }
+    }
+
+    /// Entry point of the test generated by `solution!`.
+    /// Panics if a part disagrees with an expected answer, passes if the day has no manifest.
+    pub fn test_examples(&self) {
+        let Some(outcomes) = self.run_examples().unwrap_or_else(|e| panic!("{e}")) else {
+            return;
+        };
+
+        examples::print(&outcomes);
+        let failures: Vec<String> = outcomes
+            .iter()
+            .filter(|o| !o.is_ok())
+            .map(Outcome::to_string)
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    /// Runs the parts on the examples of the day's manifest, `None` if there is no manifest.
+    fn run_examples(&self) -> Result<Option<Vec<Outcome>>, String> {
+        let Some(manifest) = examples::read_manifest(self.day)? else {
+            return Ok(None);
+        };
+
+        let parts: Vec<_> = self.parts.iter().map(|p| (p.part, &p.answer)).collect();
+        examples::run(self.day, &manifest, &parts).map(Some)
+    }
+
+    fn stress(&self, scales: &[usize], seed: u64) {
+        let generator = self.require_generator();
+        let parts: Vec<_> = self.parts.iter().map(|p| (p.part, &p.measure)).collect();
+
+        stress::run(generator, self.base_size, &parts, scales, seed);
+    }
+
+    fn require_generator(&self) -> Generator {
+        self.generator.unwrap_or_else(|| {
+            eprintln!("Day {} does not register an input generator.", self.day);
+            process::exit(1);
+        })
+    }
+}
+
+fn erase<T: Display>(func: impl Fn(&str) -> Option<T> + 'static) -> AnswerFn {
+    Box::new(move |input| func(input).map(|answer| answer.to_string()))
+}
+
+/// Unwraps a parsed command-line argument, exiting on malformed values.
+fn parse_arg<T>(result: Result<T, pico_args::Error>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("Unexpected command-line input: {e}.");
+        process::exit(1);
+    })
+}
@@ -0,0 +1,114 @@
+/// Benchmarks solution parts on generated inputs of growing size.
+/// The timings are used to estimate the empirical growth exponent of each part.
+use std::time::Duration;
+
+use crate::template::generator::{Generator, Rng};
+use crate::template::params;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Part};
+
+/// A type-erased part implementation that benchmarks a part on an input.
+pub type MeasureFn = Box<dyn Fn(&str) -> (Duration, u128)>;
+
+/// Parses a comma-separated list of scale factors, e.g. `1,2,4,8`.
+pub fn parse_scales(s: &str) -> Result<Vec<usize>, String> {
+    let scales = s
+        .split(',')
+        .map(|x| match x.trim().parse() {
+            Ok(0) | Err(_) => Err(format!("invalid scale factor `{x}`.")),
+            Ok(scale) => Ok(scale),
+        })
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    if scales.is_empty() {
+        return Err("expected at least one scale factor.".into());
+    }
+
+    Ok(scales)
+}
+
+/// Benchmarks every part at `base_size * scale` for each scale and prints the timings and growth exponents.
+pub fn run(
+    generator: Generator,
+    base_size: usize,
+    parts: &[(Part, &MeasureFn)],
+    scales: &[usize],
+    seed: u64,
+) {
+    // generated inputs have no `.params` file, parts use their defaults.
+    params::clear();
+
+    let mut results: Vec<Vec<Duration>> = vec![vec![]; parts.len()];
+    let sizes: Vec<usize> = scales.iter().map(|scale| base_size * scale).collect();
+
+    for (scale, size) in scales.iter().zip(&sizes) {
+        // every scale uses the same seed, so inputs only differ in size.
+        let input = generator(&mut Rng::new(seed), *size);
+
+        for ((part, measure), timings) in parts.iter().zip(results.iter_mut()) {
+            print!("Part {part} @ {scale}x (size {size})");
+            let (duration, samples) = measure(&input);
+            println!("\rPart {part} @ {scale}x (size {size}): {duration:.1?} @ {samples} samples");
+            timings.push(duration);
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Growth exponents{ANSI_RESET} (seed: {seed})");
+    for ((part, _), timings) in parts.iter().zip(&results) {
+        match growth_exponent(&sizes, timings) {
+            Some(k) => println!("Part {part}: {ANSI_ITALIC}O(n^{k:.2}){ANSI_RESET}"),
+            None => println!("Part {part}: -"),
+        }
+    }
+}
+
+/// Least-squares slope of `ln(duration)` over `ln(size)`. Needs at least two distinct sizes.
+fn growth_exponent(sizes: &[usize], timings: &[Duration]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = sizes
+        .iter()
+        .zip(timings)
+        .map(|(size, t)| ((*size as f64).ln(), (t.as_nanos().max(1) as f64).ln()))
+        .collect();
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{growth_exponent, parse_scales};
+
+    #[test]
+    fn parses_scales() {
+        assert_eq!(parse_scales("1,2,4,8").unwrap(), vec![1, 2, 4, 8]);
+        assert!(parse_scales("1,0").is_err());
+        assert!(parse_scales("a").is_err());
+    }
+
+    #[test]
+    fn estimates_quadratic_growth() {
+        let sizes = [100, 200, 400, 800];
+        let timings: Vec<_> = sizes
+            .iter()
+            .map(|n| Duration::from_nanos((n * n) as u64))
+            .collect();
+        let k = growth_exponent(&sizes, &timings).unwrap();
+        assert!((k - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn needs_distinct_sizes() {
+        assert!(growth_exponent(&[100], &[Duration::from_millis(1)]).is_none());
+    }
+}
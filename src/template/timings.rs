@@ -1,17 +1,53 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::config::Config;
+pub use crate::template::environment::Environment;
+use crate::template::{Day, Part};
+
+/// Version of the `timings.json` schema written by [`Timings::store_file`].
+///
+/// - version 1 (no `version` key): parts are display strings, only the total is numeric.
+/// - version 2: parts are objects with numeric nanoseconds and sample counts, every day records its environment.
+///   Environment fields beyond `profile`, `machine` and `toolchain` are optional.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Duration as printed by the runner, e.g. `74.13ns`.
+    pub display: String,
+    pub nanos: f64,
+    /// Number of benchmark samples. Unknown for timings migrated from version 1.
+    pub samples: Option<u64>,
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+impl PartTiming {
+    /// Creates a part timing from a display string such as `74.13ns`.
+    pub fn from_display(display: &str, samples: Option<u64>) -> Option<Self> {
+        Some(PartTiming {
+            display: display.to_string(),
+            nanos: parse_duration(display)?,
+            samples,
+        })
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Not known for timings migrated from version 1, or for days whose parts were timed in
+    /// different environments.
+    pub environment: Option<Environment>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -25,33 +61,102 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(&Config::get().paths.timings)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Files written with an older schema are migrated in memory, the file is only rewritten in the
+    /// current schema on the next [`Timings::store_file`].
+    pub fn read_from_file() -> Result<Self, String> {
+        let path = &Config::get().paths.timings;
+
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Timings::default()),
+            Err(e) => return Err(format!("could not read \"{}\": {e}", path.display())),
+        };
+
+        let (timings, _) =
+            Timings::parse(&contents).map_err(|e| format!("\"{}\": {e}", path.display()))?;
+
+        Ok(timings)
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
+    /// Parses timings of any supported schema version, returning the version they were stored with.
+    fn parse(s: &str) -> Result<(Self, u64), String> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
 
-        for timing in &new.data {
-            data.push(timing.clone());
+        let document = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match document.get("version") {
+            None => 1,
+            Some(JsonValue::Number(n)) if n.fract() == 0.0 && *n >= 1.0 => *n as u64,
+            Some(_) => return Err("expected `json.version` to be a positive integer.".into()),
+        };
+
+        if version > SCHEMA_VERSION {
+            return Err(format!(
+                "schema version {version} is newer than the supported version {SCHEMA_VERSION}."
+            ));
         }
 
-        for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+        let json_data = document
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let data = json_data
+            .iter()
+            .map(|timing| match version {
+                1 => Timing::from_v1(timing),
+                _ => Timing::try_from(timing),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((Timings { data }, version))
+    }
+
+    /// Merge two sets of timings part by part, overwriting `self` with `other` if present.
+    /// Parts without a timing in `other` keep the timing stored in `self`.
+    /// A day keeps no environment if its parts were timed in different environments.
+    pub fn merge(&self, new: &Self) -> Self {
+        let mut data: Vec<Timing> = self.data.clone();
+
+        for timing in &new.data {
+            let Some(merged) = data.iter_mut().find(|t| t.day == timing.day) else {
                 data.push(timing.clone());
+                continue;
+            };
+
+            // the environment describes every part of the day, so it is only replaced when no part
+            // keeps a timing from another environment. Mixed days record no environment.
+            let retimed = Part::ALL
+                .iter()
+                .all(|part| merged.part(*part).is_none() || timing.part(*part).is_some());
+            if retimed {
+                merged.environment.clone_from(&timing.environment);
+            } else if merged.environment != timing.environment {
+                merged.environment = None;
             }
+
+            for part in Part::ALL {
+                if let Some(part_timing) = timing.part(part) {
+                    merged.set_part(part, Some(part_timing.clone()));
+                }
+            }
+
+            merged.total_nanos = Part::ALL
+                .iter()
+                .filter_map(|part| merged.part(*part))
+                .map(|t| t.nanos)
+                .sum();
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -61,9 +166,28 @@ impl Timings {
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
+        Part::ALL
+            .iter()
+            .all(|part| self.is_part_complete(day, *part))
+    }
+
+    pub fn is_part_complete(&self, day: Day, part: Part) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && t.part(part).is_some())
+    }
+}
+
+/// Converts a duration printed with `{:?}` (e.g. `74.13ns`, `1.5ms` or `2s`) to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
     }
 }
 
@@ -73,6 +197,7 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION as f64));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -86,51 +211,33 @@ impl TryFrom<String> for Timings {
     type Error = String;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
-
-        let json_data = json
-            .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
-            .get("data")
-            .ok_or("expected JSON document to have key `data`.")?
-            .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
-
-        Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+        Timings::parse(&value).map(|(timings, _)| timings)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
+fn optional(value: Option<JsonValue>) -> JsonValue {
+    value.unwrap_or(JsonValue::Null)
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            optional(value.part_1.as_ref().map(JsonValue::from)),
         );
-
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            optional(value.part_2.as_ref().map(JsonValue::from)),
+        );
+        map.insert(
+            "environment".into(),
+            optional(value.environment.as_ref().map(JsonValue::from)),
         );
 
         JsonValue::Object(map)
@@ -145,82 +252,296 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+        let day = parse_day(json)?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let part = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(v) => PartTiming::try_from(v).map(Some),
+            None => Err(format!("Expected timing.{key} to be null or an object.")),
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let environment = match json.get("environment") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(Environment::try_from(v)?),
+        };
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+        Ok(Timing {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+            total_nanos: parse_total_nanos(json)?,
+            environment,
+        })
+    }
+}
+
+impl Timing {
+    /// Returns the timing of `part`, if it was benchmarked.
+    pub fn part(&self, part: Part) -> Option<&PartTiming> {
+        match part {
+            Part::One => self.part_1.as_ref(),
+            Part::Two => self.part_2.as_ref(),
+        }
+    }
+
+    /// Sets the timing of `part`.
+    pub fn set_part(&mut self, part: Part, timing: Option<PartTiming>) {
+        match part {
+            Part::One => self.part_1 = timing,
+            Part::Two => self.part_2 = timing,
+        }
+    }
+
+    /// Reads a timing stored with schema version 1, where parts are display strings.
+    fn from_v1(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
+
+        let day = parse_day(json)?;
+
+        let part = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(display)) => PartTiming::from_display(display, None)
+                .map(Some)
+                .ok_or(format!(
+                    "Expected timing.{key} to be a duration, got `{display}`."
+                )),
+            _ => Err(format!("Expected timing.{key} to be null or string.")),
+        };
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+            total_nanos: parse_total_nanos(json)?,
+            environment: None,
         })
     }
 }
 
+fn parse_day(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    json.get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.".into())
+}
+
+fn parse_total_nanos(json: &HashMap<String, JsonValue>) -> Result<f64, String> {
+    json.get("total_nanos")
+        .and_then(|v| v.get::<f64>().copied())
+        .ok_or("Expected timing.total_nanos to be a number.".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("display".into(), JsonValue::String(value.display.clone()));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert(
+            "samples".into(),
+            optional(value.samples.map(|n| JsonValue::Number(n as f64))),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let display = json
+            .get("display")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected part timing.display to be a string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part timing.nanos to be a number.")?;
+
+        let samples = match json.get("samples") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Number(n)) => Some(*n as u64),
+            Some(_) => return Err("Expected part timing.samples to be null or a number.".into()),
+        };
+
+        Ok(PartTiming {
+            display: display.clone(),
+            nanos,
+            samples,
+        })
+    }
+}
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert(
+            "toolchain".into(),
+            JsonValue::String(value.toolchain.clone()),
+        );
+        map.insert(
+            "cpu".into(),
+            optional(value.cpu.clone().map(JsonValue::String)),
+        );
+        map.insert(
+            "cores".into(),
+            optional(value.cores.map(|n| JsonValue::Number(n as f64))),
+        );
+        map.insert("target_features".into(), strings(&value.target_features));
+        map.insert("features".into(), strings(&value.features));
+        map.insert(
+            "pinned_core".into(),
+            optional(value.pinned_core.map(|n| JsonValue::Number(n as f64))),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing.environment to be a JSON object.")?;
+
+        let field = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected timing.environment.{key} to be a string."))
+        };
+
+        let number = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::Number(n)) => Ok(Some(*n as usize)),
+            Some(_) => Err(format!(
+                "Expected timing.environment.{key} to be null or a number."
+            )),
+        };
+
+        let list = |key: &str| match json.get(key) {
+            None => Ok(vec![]),
+            Some(JsonValue::Array(values)) => values
+                .iter()
+                .map(|v| v.get::<String>().cloned())
+                .collect::<Option<_>>()
+                .ok_or(format!(
+                    "Expected timing.environment.{key} to be an array of strings."
+                )),
+            Some(_) => Err(format!(
+                "Expected timing.environment.{key} to be an array of strings."
+            )),
+        };
+
+        Ok(Environment {
+            profile: field("profile")?,
+            machine: field("machine")?,
+            toolchain: field("toolchain")?,
+            cpu: json.get("cpu").and_then(|v| v.get::<String>()).cloned(),
+            cores: number("cores")?,
+            target_features: list("target_features")?,
+            features: list("features")?,
+            pinned_core: number("pinned_core")?,
+        })
+    }
+}
+
+fn strings(values: &[String]) -> JsonValue {
+    JsonValue::Array(values.iter().cloned().map(JsonValue::String).collect())
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    fn part(display: &str) -> Option<PartTiming> {
+        PartTiming::from_display(display, Some(10))
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: part("10ms"),
+                    part_2: part("20ms"),
                     total_nanos: 3e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: part("30ms"),
+                    part_2: part("40ms"),
                     total_nanos: 7e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: part("40ms"),
                     part_2: None,
                     total_nanos: 4e+10,
+                    environment: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{SCHEMA_VERSION, Timings},
+        };
 
         #[test]
-        fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
-            let timings = Timings::try_from(json).unwrap();
+        fn migrates_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#;
+            let (timings, version) = Timings::parse(json).unwrap();
+            assert_eq!(version, 1);
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.display, "1ms");
+            assert_eq!(part_1.nanos, 1_000_000_f64);
+            assert_eq!(part_1.samples, None);
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.environment, None);
+        }
+
+        #[test]
+        fn handles_v2_timings() {
+            let json = r#"{ "version": 2, "data": [{
+                "day": "01",
+                "part_1": { "display": "1.5ms", "nanos": 1500000, "samples": 640 },
+                "part_2": null,
+                "total_nanos": 1500000,
+                "environment": { "profile": "release", "machine": "box (linux-x86_64)", "toolchain": "rustc 1.95.0" }
+            }] }"#;
+            let (timings, version) = Timings::parse(json).unwrap();
+            assert_eq!(version, SCHEMA_VERSION);
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1.as_ref().unwrap().samples, Some(640));
+            assert_eq!(timing.environment.as_ref().unwrap().profile, "release");
         }
 
         #[test]
@@ -230,6 +551,12 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#;
+            assert!(Timings::parse(json).is_err());
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -247,6 +574,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{Environment, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,6 +594,35 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).format().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), timings.data.len());
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
+
+        #[test]
+        fn round_trips_environments() {
+            let mut timings = get_mock_timings();
+            let environment = Environment {
+                profile: "release".into(),
+                machine: "box (linux-x86_64)".into(),
+                toolchain: "rustc 1.95.0".into(),
+                cpu: Some("Ryzen 7 5800X".into()),
+                cores: Some(16),
+                target_features: vec!["sse2".into(), "avx2".into()],
+                features: vec!["dhat-heap".into()],
+                pinned_core: Some(2),
+            };
+            timings.data[0].environment = Some(environment.clone());
+            let json = JsonValue::from(timings).format().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].environment, Some(environment));
+        }
     }
 
     mod is_day_complete {
@@ -274,18 +631,21 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::part;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: part("1ms"),
+                    part_2: part("2ms"),
                     total_nanos: 3_000_000_000_f64,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +653,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: part("1ms"),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,20 +671,24 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                Part,
+                timings::{Environment, Timing, Timings},
+            },
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, part};
 
         #[test]
         fn handles_disjunct_timings() {
@@ -334,6 +699,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +717,10 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
+                    part_1: part("5ms"),
                     part_2: None,
-                    total_nanos: 0_f64,
+                    total_nanos: 5e+6,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,10 +728,75 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1, part("5ms"));
+            assert_eq!(merged.data[1].part_2, part("40ms"));
+            assert_eq!(merged.data[1].total_nanos, 45e+6);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        fn environment(machine: &str) -> Option<Environment> {
+            Some(Environment {
+                profile: "release".into(),
+                machine: machine.into(),
+                toolchain: "rustc 1.95.0".into(),
+                cpu: None,
+                cores: None,
+                target_features: vec![],
+                features: vec![],
+                pinned_core: None,
+            })
+        }
+
+        fn day_timing(part_1: &str, part_2: Option<&str>, machine: &str) -> Timing {
+            Timing {
+                day: day!(1),
+                part_1: part(part_1),
+                part_2: part_2.and_then(part),
+                total_nanos: 0_f64,
+                environment: environment(machine),
+            }
+        }
+
+        #[test]
+        fn replaces_environment_of_retimed_days() {
+            let timings = Timings {
+                data: vec![day_timing("1ms", Some("2ms"), "old")],
+            };
+            let other = Timings {
+                data: vec![day_timing("3ms", Some("4ms"), "new")],
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[0].environment, environment("new"));
+        }
+
+        #[test]
+        fn drops_environment_of_partially_retimed_days() {
+            let timings = Timings {
+                data: vec![day_timing("1ms", Some("2ms"), "old")],
+            };
+
+            let other = Timings {
+                data: vec![day_timing("3ms", None, "new")],
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[0].part_2, part("2ms"));
+            assert_eq!(merged.data[0].environment, None);
+
+            let same = Timings {
+                data: vec![day_timing("3ms", None, "old")],
+            };
+            let merged = timings.merge(&same);
+            assert_eq!(merged.data[0].environment, environment("old"));
+        }
+
+        #[test]
+        fn tracks_completed_parts() {
+            let timings = get_mock_timings();
+            assert!(timings.is_part_complete(day!(4), Part::One));
+            assert!(!timings.is_part_complete(day!(4), Part::Two));
+            assert!(!timings.is_part_complete(day!(3), Part::One));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,256 @@
+/// Scheduling around puzzle unlocks, used by the `today` command.
+/// Puzzles unlock at midnight EST (UTC-5) on each day of advent.
+use std::fmt::Display;
+use std::io::{Write, stdout};
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, days_in_event};
+
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// Delay before the first retry of a failed download, doubled on every further attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Source of the current time. Injectable so that scheduling can be tested without waiting.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The wall clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+fn server_offset() -> FixedOffset {
+    // NOTE: the offset is a constant within the valid range.
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap()
+}
+
+/// Number of puzzles in the event of `year`, see [`days_in_event`].
+fn event_days(year: i32) -> u8 {
+    u16::try_from(year).map_or(25, days_in_event)
+}
+
+/// Returns the day whose puzzle is current at `now`, if `now` is between the 1st and the last day of the event.
+pub fn current_day(now: DateTime<Utc>) -> Option<Day> {
+    let today = now.with_timezone(&server_offset());
+    if today.month() == 12 && today.day() <= u32::from(event_days(today.year())) {
+        Day::new(u8::try_from(today.day()).ok()?)
+    } else {
+        None
+    }
+}
+
+/// Returns the unlock time of `day` in the given `year`.
+pub fn unlock_time(year: i32, day: Day) -> Option<DateTime<Utc>> {
+    server_offset()
+        .with_ymd_and_hms(year, 12, u32::from(day.into_inner()), 0, 0, 0)
+        .single()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Returns the next puzzle that unlocks strictly after `now`.
+/// When `year` is set, only puzzles of that year's event are considered.
+pub fn next_unlock(now: DateTime<Utc>, year: Option<i32>) -> Option<(Day, DateTime<Utc>)> {
+    let current_year = now.with_timezone(&server_offset()).year();
+    let years = match year {
+        Some(year) => year..=year,
+        None => current_year..=current_year + 1,
+    };
+
+    years
+        .flat_map(|year| {
+            crate::template::all_days()
+                .take(usize::from(event_days(year)))
+                .map(move |day| (year, day))
+        })
+        .find_map(|(year, day)| {
+            let unlock = unlock_time(year, day)?;
+            (unlock > now).then_some((day, unlock))
+        })
+}
+
+/// Blocks until `unlock`, printing a live countdown.
+pub fn wait_until(clock: &impl Clock, day: Day, unlock: DateTime<Utc>) {
+    let mut stdout = stdout();
+
+    while let Ok(remaining) = (unlock - clock.now()).to_std()
+        && !remaining.is_zero()
+    {
+        print!(
+            "\r⏳ {ANSI_BOLD}Day {day}{ANSI_RESET} unlocks in {}",
+            format_countdown(remaining)
+        );
+        let _ = stdout.flush();
+
+        // sleep until the next full second so the countdown ticks evenly.
+        let tick = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        clock.sleep(if tick.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            tick
+        });
+    }
+
+    println!("\r🎄 {ANSI_BOLD}Day {day}{ANSI_RESET} is unlocked!          ");
+}
+
+fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let (hours, mins, secs) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{mins:02}:{secs:02}")
+    } else {
+        format!("{hours:02}:{mins:02}:{secs:02}")
+    }
+}
+
+/// Calls `f` until it succeeds or `attempts` are exhausted, with exponential backoff between calls.
+pub fn retry_with_backoff<T, E: Display>(
+    clock: &impl Clock,
+    attempts: u32,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut delay = RETRY_BASE_DELAY;
+    let mut attempt = 1;
+
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts => return Err(e),
+            Err(e) => {
+                eprintln!("{e} Retrying in {}s…", delay.as_secs());
+                clock.sleep(delay);
+                delay = (delay * 2).min(RETRY_MAX_DELAY);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{Clock, current_day, next_unlock, retry_with_backoff, wait_until};
+    use crate::day;
+
+    struct FakeClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: DateTime<Utc>) -> Self {
+            Self {
+                now: Cell::new(now),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, month, day, hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn resolves_current_day_in_server_time() {
+        // 04:59 UTC is still the previous day in EST.
+        assert_eq!(current_day(utc(12, 5, 4, 59)), Some(day!(4)));
+        assert_eq!(current_day(utc(12, 5, 5, 0)), Some(day!(5)));
+        assert_eq!(current_day(utc(11, 30, 12, 0)), None);
+        assert_eq!(current_day(utc(12, 12, 12, 0)), Some(day!(12)));
+        assert_eq!(current_day(utc(12, 13, 12, 0)), None);
+        assert_eq!(current_day(utc(12, 26, 12, 0)), None);
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(
+            next_unlock(utc(11, 20, 0, 0), None),
+            Some((day!(1), utc(12, 1, 5, 0)))
+        );
+        assert_eq!(
+            next_unlock(utc(12, 5, 5, 0), None),
+            Some((day!(6), utc(12, 6, 5, 0)))
+        );
+        assert_eq!(next_unlock(utc(12, 27, 0, 0), Some(2025)), None);
+        // events since 2025 end on day 12.
+        assert_eq!(next_unlock(utc(12, 12, 5, 0), Some(2025)), None);
+        assert_eq!(
+            next_unlock(utc(12, 12, 5, 0), None),
+            Some((day!(1), Utc.with_ymd_and_hms(2026, 12, 1, 5, 0, 0).unwrap()))
+        );
+        assert_eq!(
+            next_unlock(Utc.with_ymd_and_hms(2024, 12, 20, 5, 0, 0).unwrap(), None),
+            Some((
+                day!(21),
+                Utc.with_ymd_and_hms(2024, 12, 21, 5, 0, 0).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock::at(utc(12, 1, 4, 59));
+        wait_until(&clock, day!(1), utc(12, 1, 5, 0));
+        assert_eq!(clock.now(), utc(12, 1, 5, 0));
+        assert_eq!(clock.sleeps.borrow().len(), 60);
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(utc(12, 1, 5, 0));
+        let mut calls = 0;
+        let result: Result<u32, &str> = retry_with_backoff(&clock, 5, || {
+            calls += 1;
+            if calls < 4 {
+                Err("unavailable.")
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result, Ok(4));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [2, 4, 8].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[test]
+    fn gives_up_after_attempts() {
+        let clock = FakeClock::at(utc(12, 1, 5, 0));
+        let result: Result<(), &str> = retry_with_backoff(&clock, 3, || Err("unavailable."));
+        assert!(result.is_err());
+        assert_eq!(clock.sleeps.borrow().len(), 2);
+    }
+}
@@ -0,0 +1,160 @@
+/// Side-by-side comparison of alternative implementations ("variants") of a part.
+/// Variants are registered with the `variant_one` / `variant_two` hooks of the `solution!` macro
+/// and compared with `--variants`. The function registered as the part itself stays the default,
+/// which is what `time` and the README benchmarks use.
+use std::time::Duration;
+
+use crate::template::crosscheck::AnswerFn;
+use crate::template::stress::MeasureFn;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Part};
+
+/// Name of the variant that is registered as the part itself.
+pub const DEFAULT_VARIANT: &str = "default";
+
+pub struct Variant {
+    pub name: &'static str,
+    pub answer: AnswerFn,
+    pub measure: MeasureFn,
+}
+
+struct Row {
+    name: &'static str,
+    answer: Option<String>,
+    duration: Duration,
+    samples: u128,
+}
+
+/// Runs and benchmarks all variants of a part and prints a comparison table.
+/// The first variant is the default, returns whether all others agree with its answer.
+pub fn compare(part: Part, variants: &[Variant], input: &str) -> bool {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    let rows: Vec<Row> = variants
+        .iter()
+        .map(|variant| {
+            print!("{}", variant.name);
+            let answer = (variant.answer)(input);
+            let (duration, samples) = (variant.measure)(input);
+            print!("\r\x1b[2K");
+            Row {
+                name: variant.name,
+                answer,
+                duration,
+                samples,
+            }
+        })
+        .collect();
+
+    let Some(default) = rows.first() else {
+        return true;
+    };
+
+    let disagreeing: Vec<&str> = rows
+        .iter()
+        .filter(|row| row.answer != default.answer)
+        .map(|row| row.name)
+        .collect();
+
+    let table: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let mut answer = display_answer(row.answer.as_deref());
+            if row.answer != default.answer {
+                answer.push_str(" ✖");
+            }
+            let relative = if default.duration.is_zero() {
+                "-".to_string()
+            } else {
+                format!(
+                    "{:.2}x",
+                    row.duration.as_secs_f64() / default.duration.as_secs_f64()
+                )
+            };
+            [
+                row.name.to_string(),
+                answer,
+                format!("{:.1?} @ {} samples", row.duration, row.samples),
+                relative,
+            ]
+        })
+        .collect();
+
+    print_table(&["Variant", "Answer", "Time", "Relative"], &table);
+
+    if disagreeing.is_empty() {
+        println!("All variants agree.\n");
+        true
+    } else {
+        println!(
+            "✖ Variants disagree with `{}`: {}\n",
+            default.name,
+            disagreeing.join(", ")
+        );
+        false
+    }
+}
+
+fn display_answer(answer: Option<&str>) -> String {
+    match answer {
+        None => "-".into(),
+        Some(answer) if answer.contains('\n') => {
+            format!("({} lines)", answer.lines().count())
+        }
+        Some(answer) => answer.into(),
+    }
+}
+
+fn print_table(header: &[&str; 4], rows: &[[String; 4]]) {
+    let mut widths = header.map(|h| h.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: [&str; 4]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect();
+        println!("  {}", cells.join("  ").trim_end());
+    };
+
+    line(*header);
+    for row in rows {
+        line([&row[0], &row[1], &row[2], &row[3]]);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Variant, compare};
+    use crate::template::Part;
+
+    fn variant(name: &'static str, answer: &'static str) -> Variant {
+        Variant {
+            name,
+            answer: Box::new(move |_| Some(answer.to_string())),
+            measure: Box::new(|_| (Duration::from_micros(1), 10)),
+        }
+    }
+
+    #[test]
+    fn detects_agreeing_variants() {
+        let variants = [variant("default", "42"), variant("fast", "42")];
+        assert!(compare(Part::One, &variants, ""));
+    }
+
+    #[test]
+    fn detects_disagreeing_variants() {
+        let variants = [
+            variant("default", "42"),
+            variant("fast", "42"),
+            variant("broken", "41"),
+        ];
+        assert!(!compare(Part::Two, &variants, ""));
+    }
+}
@@ -0,0 +1,287 @@
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+use crate::utils::region::Connectivity;
+use rayon::prelude::*;
+
+/// Number of steps taken and cells changed in each of them.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Stats {
+    pub steps: usize,
+    pub changed: Vec<usize>,
+}
+
+impl Stats {
+    /// Number of cell changes over all steps.
+    pub fn total_changed(&self) -> usize {
+        self.changed.iter().sum()
+    }
+}
+
+/// A cellular automaton over a grid. Every cell is updated by `rule(cell, n)`, where `n` is
+/// the number of neighbors that satisfy `counted`.
+pub struct Automaton<T, C, R> {
+    pub grid: Grid<T>,
+    buffer: Grid<T>,
+    counted: C,
+    rule: R,
+    connectivity: Connectivity,
+    parallel: bool,
+    pub stats: Stats,
+}
+
+impl<T, C, R> Automaton<T, C, R>
+where
+    T: Clone + PartialEq + Send + Sync,
+    C: Fn(&T) -> bool + Sync,
+    R: Fn(&T, u32) -> T + Sync,
+{
+    /// An automaton that counts all 8 neighbors and steps sequentially.
+    pub fn new(grid: Grid<T>, counted: C, rule: R) -> Self {
+        Automaton {
+            buffer: grid.clone(),
+            grid,
+            counted,
+            rule,
+            connectivity: Connectivity::Eight,
+            parallel: false,
+            stats: Stats::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+
+    /// Computes the rows of synchronous steps in parallel, which pays off for large grids.
+    #[must_use]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Updates all cells at once from the previous generation. Returns the number of cells
+    /// that changed.
+    pub fn step(&mut self) -> usize {
+        let width = self.grid.width as usize;
+        let changed = if width == 0 {
+            0
+        } else {
+            let Automaton {
+                grid,
+                buffer,
+                counted,
+                rule,
+                connectivity,
+                ..
+            } = self;
+            let update_row = |(y, row): (usize, &mut [T])| {
+                let mut changed = 0;
+                for (x, out) in row.iter_mut().enumerate() {
+                    let point = Point::new(x as i64, y as i64);
+                    let next = rule(&grid[point], count(grid, *connectivity, counted, point));
+                    changed += usize::from(next != grid[point]);
+                    *out = next;
+                }
+                changed
+            };
+
+            if self.parallel {
+                buffer
+                    .bytes
+                    .par_chunks_mut(width)
+                    .enumerate()
+                    .map(update_row)
+                    .sum()
+            } else {
+                buffer
+                    .bytes
+                    .chunks_mut(width)
+                    .enumerate()
+                    .map(update_row)
+                    .sum()
+            }
+        };
+
+        std::mem::swap(&mut self.grid, &mut self.buffer);
+        self.stats.steps += 1;
+        self.stats.changed.push(changed);
+        changed
+    }
+
+    /// Takes synchronous steps until one changes nothing or `max_steps` were taken.
+    /// The final step that changes nothing is included in the stats.
+    pub fn run(&mut self, max_steps: Option<usize>) -> &Stats {
+        let mut steps = 0;
+        while max_steps.is_none_or(|max| steps < max) {
+            steps += 1;
+            if self.step() == 0 {
+                break;
+            }
+        }
+        &self.stats
+    }
+
+    /// Updates cells one at a time in place until nothing changes, revisiting only the
+    /// neighbors of changed cells. This reaches the same fixpoint as [`Automaton::run`] for
+    /// rules where the order of updates does not matter, e.g. monotone ones like erosion.
+    /// Counts as a single step in the stats. Returns the number of cell changes.
+    pub fn settle(&mut self) -> usize {
+        let mut queued = Grid::new(self.grid.width, self.grid.height, true);
+        let mut todo: Vec<Point> = self.grid.points().collect();
+        let mut changed = 0;
+
+        while let Some(point) = todo.pop() {
+            queued[point] = false;
+
+            let count = count(&self.grid, self.connectivity, &self.counted, point);
+            let next = (self.rule)(&self.grid[point], count);
+            if next == self.grid[point] {
+                continue;
+            }
+
+            self.grid[point] = next;
+            changed += 1;
+
+            for neighbor in neighbors(&self.grid, self.connectivity, point) {
+                if !queued[neighbor] {
+                    queued[neighbor] = true;
+                    todo.push(neighbor);
+                }
+            }
+            if !queued[point] {
+                queued[point] = true;
+                todo.push(point);
+            }
+        }
+
+        self.stats.steps += 1;
+        self.stats.changed.push(changed);
+        changed
+    }
+}
+
+#[inline]
+fn neighbors<T>(
+    grid: &Grid<T>,
+    connectivity: Connectivity,
+    point: Point,
+) -> impl Iterator<Item = Point> {
+    connectivity
+        .offsets()
+        .iter()
+        .map(move |offset| point + *offset)
+        .filter(|p| grid.in_bounds(p))
+}
+
+/// Number of neighbors of `point` that satisfy `counted`.
+#[inline]
+fn count<T>(
+    grid: &Grid<T>,
+    connectivity: Connectivity,
+    counted: &impl Fn(&T) -> bool,
+    point: Point,
+) -> u32 {
+    neighbors(grid, connectivity, point)
+        .filter(|p| counted(&grid[*p]))
+        .count() as u32
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Automaton, Stats};
+    use crate::utils::grid::Grid;
+
+    const GLIDER: &str = ".#......\n..#.....\n###.....\n........\n........\n........\n";
+
+    const ROLLS: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+    fn life(
+        grid: &str,
+        parallel: bool,
+    ) -> Automaton<u8, impl Fn(&u8) -> bool, impl Fn(&u8, u32) -> u8> {
+        let alive = |c: &u8| *c == b'#';
+        let rule = move |c: &u8, n: u32| {
+            if n == 3 || (alive(c) && n == 2) {
+                b'#'
+            } else {
+                b'.'
+            }
+        };
+        Automaton::new(Grid::parse(grid), alive, rule).parallel(parallel)
+    }
+
+    fn erosion(parallel: bool) -> Automaton<u8, impl Fn(&u8) -> bool, impl Fn(&u8, u32) -> u8> {
+        let roll = |c: &u8| *c == b'@';
+        let rule = move |c: &u8, n: u32| if roll(c) && n < 4 { b'.' } else { *c };
+        Automaton::new(Grid::parse(ROLLS), roll, rule).parallel(parallel)
+    }
+
+    #[test]
+    fn steps_gliders() {
+        for parallel in [false, true] {
+            let mut automaton = life(GLIDER, parallel);
+            let stats = automaton.run(Some(4));
+            assert_eq!(
+                *stats,
+                Stats {
+                    steps: 4,
+                    changed: vec![4; 4],
+                }
+            );
+            // after 4 generations, the glider has moved one cell down and to the right.
+            assert_eq!(
+                automaton.grid.to_string(),
+                "........\n..#.....\n...#....\n.###....\n........\n........\n"
+            );
+        }
+    }
+
+    #[test]
+    fn stops_when_stable() {
+        let mut blinker = life(".....\n..#..\n..#..\n..#..\n.....\n", false);
+        blinker.run(Some(2));
+        assert_eq!(
+            blinker.grid,
+            Grid::parse(".....\n..#..\n..#..\n..#..\n.....\n")
+        );
+
+        let mut block = life("....\n.##.\n.##.\n....\n", false);
+        assert_eq!(block.run(None).changed, [0]);
+    }
+
+    #[test]
+    fn settles_like_synchronous_steps() {
+        let mut sequential = erosion(false);
+        let mut parallel = erosion(true);
+        let mut worklist = erosion(false);
+
+        let stats = sequential.run(None);
+        assert_eq!(stats.changed, [13, 12, 7, 5, 2, 1, 1, 1, 1, 0]);
+        assert_eq!(stats.steps, 10);
+        assert_eq!(parallel.run(None), &sequential.stats);
+
+        assert_eq!(worklist.settle(), 43);
+        assert_eq!(
+            worklist.stats,
+            Stats {
+                steps: 1,
+                changed: vec![43],
+            }
+        );
+        assert_eq!(worklist.grid, sequential.grid);
+        assert_eq!(parallel.grid, sequential.grid);
+    }
+}
@@ -0,0 +1,705 @@
+use crate::utils::grid::Grid;
+use crate::utils::grid3d::Grid3D;
+use crate::utils::point::{DIAGONAL, Point};
+use crate::utils::point3d::{NEIGHBORS_26, Point3D};
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+/// Word-level access shared by [`BitGrid`] and [`BitGrid3D`].
+///
+/// Cells are stored in rows of whole `u64` words, bit `x % 64` of word `x / 64` holds column
+/// `x`. Bits past the width of a row are always zero.
+pub trait Words: Clone {
+    fn words(&self) -> &[u64];
+    fn words_mut(&mut self) -> &mut [u64];
+    /// Clears the bits past the width of every row.
+    fn clear_padding(&mut self);
+
+    /// A grid of the same size with all cells cleared.
+    fn cleared(&self) -> Self {
+        let mut grid = self.clone();
+        grid.words_mut().fill(0);
+        grid
+    }
+
+    /// Number of set cells.
+    fn count_ones(&self) -> usize {
+        self.words().iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words().iter().all(|w| *w == 0)
+    }
+}
+
+/// Number of words of a row of `width` bits.
+#[inline]
+fn row_words(width: i64) -> usize {
+    (width as usize).div_ceil(64)
+}
+
+/// Mask of the valid bits in the last word of a row of `width` bits.
+#[inline]
+fn last_word_mask(width: i64) -> u64 {
+    match width % 64 {
+        0 => u64::MAX,
+        bits => (1 << bits) - 1,
+    }
+}
+
+/// Writes `src` moved by `dx` columns into `dst`, e.g. `dx = 1` moves every cell one column
+/// to the right. Cells that move past either end are dropped.
+fn shift_row(src: &[u64], dst: &mut [u64], dx: i64) {
+    let word_shift = dx.abs() / 64;
+    let bit_shift = (dx.abs() % 64) as u32;
+    let word = |i: i64| usize::try_from(i).map_or(0, |i| src.get(i).copied().unwrap_or(0));
+
+    for (i, out) in dst.iter_mut().enumerate() {
+        let i = i as i64;
+        *out = if dx >= 0 {
+            let (hi, lo) = (word(i - word_shift), word(i - word_shift - 1));
+            match bit_shift {
+                0 => hi,
+                b => (hi << b) | (lo >> (64 - b)),
+            }
+        } else {
+            let (lo, hi) = (word(i + word_shift), word(i + word_shift + 1));
+            match bit_shift {
+                0 => lo,
+                b => (lo >> b) | (hi << (64 - b)),
+            }
+        };
+    }
+}
+
+/// Per-cell counts of set neighbors, stored as bit planes: bit `k` of a cell's count is the
+/// cell's bit in `planes[k]`.
+#[derive(Clone, Debug)]
+pub struct NeighborCounts<G> {
+    pub planes: Vec<G>,
+}
+
+impl<G: Words> NeighborCounts<G> {
+    /// Adds up the given grids with a bit-sliced ripple-carry adder.
+    fn sum(grids: impl IntoIterator<Item = G>, max: usize, shape: &G) -> Self {
+        let bits = (usize::BITS - max.leading_zeros()) as usize;
+        let mut planes = vec![shape.cleared(); bits.max(1)];
+
+        for grid in grids {
+            let mut carry = grid.words().to_vec();
+            for plane in &mut planes {
+                for (p, c) in plane.words_mut().iter_mut().zip(&mut carry) {
+                    let next = *p & *c;
+                    *p ^= *c;
+                    *c = next;
+                }
+            }
+        }
+
+        NeighborCounts { planes }
+    }
+
+    /// Cells with exactly `n` set neighbors.
+    pub fn equal(&self, n: u32) -> G {
+        let mut result = self.planes[0].clone();
+        result.words_mut().fill(u64::MAX);
+
+        // NOTE: counts that need more planes than there are cannot occur.
+        if n >> self.planes.len() != 0 {
+            result.words_mut().fill(0);
+            return result;
+        }
+
+        for (k, plane) in self.planes.iter().enumerate() {
+            let bit_set = n >> k & 1 == 1;
+            for (r, p) in result.words_mut().iter_mut().zip(plane.words()) {
+                *r &= if bit_set { *p } else { !*p };
+            }
+        }
+
+        result.clear_padding();
+        result
+    }
+
+    /// Cells with fewer than `n` set neighbors.
+    pub fn less_than(&self, n: u32) -> G {
+        let mut result = self.planes[0].cleared();
+        for i in 0..n {
+            let equal = self.equal(i);
+            for (r, e) in result.words_mut().iter_mut().zip(equal.words()) {
+                *r |= *e;
+            }
+        }
+        result
+    }
+
+    /// The next generation of a Life-like automaton, where `rule(alive, neighbors)` tells
+    /// whether a cell is alive afterwards.
+    pub fn step(&self, alive: &G, rule: impl Fn(bool, u32) -> bool) -> G {
+        let mut result = alive.cleared();
+        let max = (1 << self.planes.len()) - 1;
+
+        for n in 0..=max {
+            let (survives, born) = (rule(true, n), rule(false, n));
+            if !survives && !born {
+                continue;
+            }
+
+            let equal = self.equal(n);
+            for ((r, e), a) in result
+                .words_mut()
+                .iter_mut()
+                .zip(equal.words())
+                .zip(alive.words())
+            {
+                *r |= match (survives, born) {
+                    (true, true) => *e,
+                    (true, false) => *e & *a,
+                    _ => *e & !*a,
+                };
+            }
+        }
+
+        result.clear_padding();
+        result
+    }
+}
+
+macro_rules! impl_set_ops {
+    ($grid:ty) => {
+        impl BitAndAssign<&$grid> for $grid {
+            fn bitand_assign(&mut self, rhs: &$grid) {
+                self.words
+                    .iter_mut()
+                    .zip(&rhs.words)
+                    .for_each(|(a, b)| *a &= *b);
+            }
+        }
+
+        impl BitOrAssign<&$grid> for $grid {
+            fn bitor_assign(&mut self, rhs: &$grid) {
+                self.words
+                    .iter_mut()
+                    .zip(&rhs.words)
+                    .for_each(|(a, b)| *a |= *b);
+            }
+        }
+
+        impl BitXorAssign<&$grid> for $grid {
+            fn bitxor_assign(&mut self, rhs: &$grid) {
+                self.words
+                    .iter_mut()
+                    .zip(&rhs.words)
+                    .for_each(|(a, b)| *a ^= *b);
+            }
+        }
+
+        impl BitAnd for &$grid {
+            type Output = $grid;
+
+            fn bitand(self, rhs: Self) -> $grid {
+                let mut result = self.clone();
+                result &= rhs;
+                result
+            }
+        }
+
+        impl BitOr for &$grid {
+            type Output = $grid;
+
+            fn bitor(self, rhs: Self) -> $grid {
+                let mut result = self.clone();
+                result |= rhs;
+                result
+            }
+        }
+
+        impl BitXor for &$grid {
+            type Output = $grid;
+
+            fn bitxor(self, rhs: Self) -> $grid {
+                let mut result = self.clone();
+                result ^= rhs;
+                result
+            }
+        }
+
+        impl Not for &$grid {
+            type Output = $grid;
+
+            fn not(self) -> $grid {
+                let mut result = self.clone();
+                result.words.iter_mut().for_each(|w| *w = !*w);
+                result.clear_padding();
+                result
+            }
+        }
+
+        impl Words for $grid {
+            #[inline]
+            fn words(&self) -> &[u64] {
+                &self.words
+            }
+
+            #[inline]
+            fn words_mut(&mut self) -> &mut [u64] {
+                &mut self.words
+            }
+
+            fn clear_padding(&mut self) {
+                let mask = last_word_mask(self.width);
+                let row_words = self.row_words;
+                if row_words > 0 {
+                    for row in self.words.chunks_exact_mut(row_words) {
+                        row[row_words - 1] &= mask;
+                    }
+                }
+            }
+        }
+
+        impl $grid {
+            /// Cells that are set in `self` but not in `other`.
+            #[must_use]
+            pub fn and_not(&self, other: &$grid) -> $grid {
+                let mut result = self.clone();
+                result
+                    .words
+                    .iter_mut()
+                    .zip(&other.words)
+                    .for_each(|(a, b)| *a &= !*b);
+                result
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A 2D grid of bits with word-level operations.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid {
+    pub width: i64,
+    pub height: i64,
+    row_words: usize,
+    words: Vec<u64>,
+}
+
+impl_set_ops!(BitGrid);
+
+impl BitGrid {
+    pub fn new(width: i64, height: i64) -> Self {
+        let row_words = row_words(width);
+
+        BitGrid {
+            width,
+            height,
+            row_words,
+            words: vec![0; row_words * height as usize],
+        }
+    }
+
+    /// Sets the cells of a grid that satisfy `predicate`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut predicate: impl FnMut(&T) -> bool) -> Self {
+        let mut bits = BitGrid::new(grid.width, grid.height);
+        for (point, cell) in grid.cells() {
+            if predicate(cell) {
+                bits.set(point, true);
+            }
+        }
+        bits
+    }
+
+    /// A grid with `on` for set cells and `off` for the others.
+    pub fn to_grid<T: Clone>(&self, on: T, off: T) -> Grid<T> {
+        let mut grid = Grid::new(self.width, self.height, off);
+        for point in grid.points() {
+            if self.get(point) {
+                grid[point] = on.clone();
+            }
+        }
+        grid
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, p: &Point) -> bool {
+        p.x >= 0 && p.y >= 0 && p.x < self.width && p.y < self.height
+    }
+
+    /// Word index and bit of a point. Checked in release builds too, since a point past the
+    /// width would silently set padding bits.
+    #[inline]
+    fn locate(&self, p: Point) -> (usize, u64) {
+        assert!(self.in_bounds(&p), "{p} is out of bounds");
+        let idx = p.y as usize * self.row_words + p.x as usize / 64;
+        (idx, 1 << (p.x % 64))
+    }
+
+    #[inline]
+    pub fn get(&self, p: Point) -> bool {
+        let (idx, bit) = self.locate(p);
+        self.words[idx] & bit != 0
+    }
+
+    #[inline]
+    pub fn set(&mut self, p: Point, value: bool) {
+        let (idx, bit) = self.locate(p);
+        if value {
+            self.words[idx] |= bit;
+        } else {
+            self.words[idx] &= !bit;
+        }
+    }
+
+    #[inline]
+    pub fn toggle(&mut self, p: Point) {
+        let (idx, bit) = self.locate(p);
+        self.words[idx] ^= bit;
+    }
+
+    /// The words of row `y`, see [`Words`] for the layout.
+    #[inline]
+    pub fn row(&self, y: i64) -> &[u64] {
+        let start = y as usize * self.row_words;
+        &self.words[start..start + self.row_words]
+    }
+
+    /// The words of row `y`. Callers clear the padding afterwards.
+    #[inline]
+    fn row_mut(&mut self, y: i64) -> &mut [u64] {
+        let start = y as usize * self.row_words;
+        &mut self.words[start..start + self.row_words]
+    }
+
+    pub fn count_row(&self, y: i64) -> usize {
+        self.row(y).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Set cells in reading order.
+    pub fn ones(&self) -> impl Iterator<Item = Point> {
+        (0..self.height).flat_map(move |y| {
+            self.row(y).iter().enumerate().flat_map(move |(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as i64;
+                    word &= word - 1;
+                    Some(Point::new(i as i64 * 64 + bit, y))
+                })
+            })
+        })
+    }
+
+    /// The grid moved by `offset`, cells that move out of bounds are dropped.
+    #[must_use]
+    pub fn shift(&self, offset: Point) -> Self {
+        let mut result = self.cleared();
+        for y in 0..self.height {
+            let source = y - offset.y;
+            if (0..self.height).contains(&source) {
+                shift_row(self.row(source), result.row_mut(y), offset.x);
+            }
+        }
+        result.clear_padding();
+        result
+    }
+
+    /// Counts of set neighbors among all 8 neighbors of every cell.
+    pub fn neighbor_counts(&self) -> NeighborCounts<Self> {
+        self.counts_by(DIAGONAL)
+    }
+
+    /// Counts of set neighbors at custom offsets.
+    pub fn counts_by<const N: usize>(&self, offsets: [Point; N]) -> NeighborCounts<Self> {
+        // NOTE: a neighbor at `offset` ends up on a cell after moving by `-offset`.
+        let shifted = offsets
+            .into_iter()
+            .map(|o| self.shift(Point::new(-o.x, -o.y)));
+        NeighborCounts::sum(shifted, N, self)
+    }
+
+    /// The next generation of a Life-like automaton over all 8 neighbors.
+    #[must_use]
+    pub fn life_step(&self, rule: impl Fn(bool, u32) -> bool) -> Self {
+        self.neighbor_counts().step(self, rule)
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let c = if self.get(Point::new(x, y)) { '#' } else { '.' };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A 3D grid of bits with word-level operations. Rows run along x and are stored z-major.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid3D {
+    pub width: i64,
+    pub height: i64,
+    pub depth: i64,
+    row_words: usize,
+    words: Vec<u64>,
+}
+
+impl_set_ops!(BitGrid3D);
+
+impl BitGrid3D {
+    pub fn new(width: i64, height: i64, depth: i64) -> Self {
+        let row_words = row_words(width);
+
+        BitGrid3D {
+            width,
+            height,
+            depth,
+            row_words,
+            words: vec![0; row_words * (height * depth) as usize],
+        }
+    }
+
+    /// Sets the cells of a grid that satisfy `predicate`.
+    pub fn from_grid<T>(grid: &Grid3D<T>, mut predicate: impl FnMut(&T) -> bool) -> Self {
+        let mut bits = BitGrid3D::new(grid.width, grid.height, grid.depth);
+        for point in grid.points() {
+            if predicate(&grid[point]) {
+                bits.set(point, true);
+            }
+        }
+        bits
+    }
+
+    /// A grid with `on` for set cells and `off` for the others.
+    pub fn to_grid<T: Clone>(&self, on: T, off: T) -> Grid3D<T> {
+        let mut grid = Grid3D::new(self.width, self.height, self.depth, off);
+        for point in grid.points() {
+            if self.get(point) {
+                grid[point] = on.clone();
+            }
+        }
+        grid
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, p: &Point3D) -> bool {
+        p.x >= 0
+            && p.y >= 0
+            && p.z >= 0
+            && p.x < self.width
+            && p.y < self.height
+            && p.z < self.depth
+    }
+
+    #[inline]
+    fn row_index(&self, y: i64, z: i64) -> usize {
+        (z * self.height + y) as usize * self.row_words
+    }
+
+    /// Word index and bit of a point, checked like [`BitGrid`]'s.
+    #[inline]
+    fn locate(&self, p: Point3D) -> (usize, u64) {
+        assert!(self.in_bounds(&p), "{p:?} is out of bounds");
+        (
+            self.row_index(p.y, p.z) + p.x as usize / 64,
+            1 << (p.x % 64),
+        )
+    }
+
+    #[inline]
+    pub fn get(&self, p: Point3D) -> bool {
+        let (idx, bit) = self.locate(p);
+        self.words[idx] & bit != 0
+    }
+
+    #[inline]
+    pub fn set(&mut self, p: Point3D, value: bool) {
+        let (idx, bit) = self.locate(p);
+        if value {
+            self.words[idx] |= bit;
+        } else {
+            self.words[idx] &= !bit;
+        }
+    }
+
+    #[inline]
+    pub fn toggle(&mut self, p: Point3D) {
+        let (idx, bit) = self.locate(p);
+        self.words[idx] ^= bit;
+    }
+
+    /// The words of the row at `y` and `z`, see [`Words`] for the layout.
+    #[inline]
+    pub fn row(&self, y: i64, z: i64) -> &[u64] {
+        let start = self.row_index(y, z);
+        &self.words[start..start + self.row_words]
+    }
+
+    /// The grid moved by `offset`, cells that move out of bounds are dropped.
+    #[must_use]
+    pub fn shift(&self, offset: Point3D) -> Self {
+        let mut result = self.cleared();
+        for z in 0..self.depth {
+            for y in 0..self.height {
+                let (source_y, source_z) = (y - offset.y, z - offset.z);
+                if (0..self.height).contains(&source_y) && (0..self.depth).contains(&source_z) {
+                    let start = result.row_index(y, z);
+                    let dst = &mut result.words[start..start + self.row_words];
+                    shift_row(self.row(source_y, source_z), dst, offset.x);
+                }
+            }
+        }
+        result.clear_padding();
+        result
+    }
+
+    /// Counts of set neighbors among all 26 neighbors of every cell.
+    pub fn neighbor_counts(&self) -> NeighborCounts<Self> {
+        self.counts_by(NEIGHBORS_26)
+    }
+
+    /// Counts of set neighbors at custom offsets.
+    pub fn counts_by<const N: usize>(&self, offsets: [Point3D; N]) -> NeighborCounts<Self> {
+        let shifted = offsets
+            .into_iter()
+            .map(|o| self.shift(Point3D::new(-o.x, -o.y, -o.z)));
+        NeighborCounts::sum(shifted, N, self)
+    }
+
+    /// The next generation of a Life-like automaton over all 26 neighbors.
+    #[must_use]
+    pub fn life_step(&self, rule: impl Fn(bool, u32) -> bool) -> Self {
+        self.neighbor_counts().step(self, rule)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitGrid, BitGrid3D, Words};
+    use crate::utils::grid::Grid;
+    use crate::utils::grid3d::Grid3D;
+    use crate::utils::point::Point;
+    use crate::utils::point3d::Point3D;
+
+    const WIDTHS: [i64; 5] = [1, 63, 64, 65, 130];
+
+    /// A fixed pseudo-random pattern with roughly 3 in 8 cells set.
+    fn is_set(x: i64, y: i64, z: i64) -> bool {
+        let hash = (x as u64)
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            .wrapping_add((y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F))
+            .wrapping_add((z as u64).wrapping_mul(0x1656_67B1_9E37_79F9));
+        (hash ^ (hash >> 29)).wrapping_mul(0xBF58_476D_1CE4_E5B9) >> 61 < 3
+    }
+
+    fn pattern(width: i64, height: i64) -> Grid<bool> {
+        let mut grid = Grid::new(width, height, false);
+        for point in grid.points() {
+            grid[point] = is_set(point.x, point.y, 0);
+        }
+        grid
+    }
+
+    fn conway(alive: bool, n: u32) -> bool {
+        n == 3 || (alive && n == 2)
+    }
+
+    #[test]
+    fn counts_neighbors_like_grid() {
+        for width in WIDTHS {
+            let grid = pattern(width, 5);
+            let bits = BitGrid::from_grid(&grid, |c| *c);
+            let counts = bits.neighbor_counts();
+            let next = bits.life_step(conway);
+
+            for point in grid.points() {
+                let expected = grid.neighbor_cells(point).filter(|(_, c)| **c).count() as u32;
+                for n in 0..=8 {
+                    assert_eq!(
+                        counts.equal(n).get(point),
+                        expected == n,
+                        "{point} in {width}"
+                    );
+                    assert_eq!(counts.less_than(n).get(point), expected < n);
+                }
+                assert_eq!(next.get(point), conway(grid[point], expected));
+            }
+
+            // padding bits stay clear, so counts only cover cells in bounds.
+            assert_eq!(counts.equal(0).count_ones(), counts.equal(0).ones().count());
+            assert_eq!(next.count_ones(), next.ones().count());
+        }
+    }
+
+    #[test]
+    fn counts_neighbors_like_grid3d() {
+        for width in WIDTHS {
+            let mut grid = Grid3D::new(width, 3, 3, false);
+            for point in grid.points() {
+                grid[point] = is_set(point.x, point.y, point.z);
+            }
+            let bits = BitGrid3D::from_grid(&grid, |c| *c);
+            let counts = bits.neighbor_counts();
+
+            for point in grid.points() {
+                let expected = grid.neighbors_26(&point).filter(|p| grid[*p]).count() as u32;
+                assert!(counts.equal(expected).get(point), "{point:?} in {width}");
+            }
+            assert_eq!(bits.to_grid(true, false), grid);
+        }
+    }
+
+    #[test]
+    fn shifts_across_words() {
+        let grid = pattern(130, 3);
+        let bits = BitGrid::from_grid(&grid, |c| *c);
+
+        for dx in [
+            -200, -130, -65, -64, -63, -1, 0, 1, 63, 64, 65, 129, 130, 200,
+        ] {
+            for dy in [-1, 0, 2] {
+                let offset = Point::new(dx, dy);
+                let shifted = bits.shift(offset);
+                for point in grid.points() {
+                    let source = point - offset;
+                    let expected = grid.in_bounds(&source) && grid[source];
+                    assert_eq!(shifted.get(point), expected, "{point} moved by {offset}");
+                }
+                assert_eq!(shifted.count_ones(), shifted.ones().count());
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_padding_clear() {
+        let mut bits = BitGrid::new(65, 2);
+        bits.set(Point::new(64, 1), true);
+        assert_eq!((!&bits).count_ones(), 65 * 2 - 1);
+        assert_eq!(bits.ones().collect::<Vec<_>>(), [Point::new(64, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn rejects_points_past_the_width() {
+        BitGrid::new(65, 2).set(Point::new(65, 0), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn rejects_negative_points() {
+        BitGrid::new(65, 2).get(Point::new(-1, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn rejects_points_past_the_width_3d() {
+        BitGrid3D::new(64, 2, 2).set(Point3D::new(64, 0, 0), true);
+    }
+}
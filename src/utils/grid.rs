@@ -1,5 +1,9 @@
-use crate::utils::point::Point;
+use crate::utils::point::{DIAGONAL, DOWN, LEFT, Point, RIGHT, UP};
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Grid<T> {
@@ -11,34 +15,191 @@ pub struct Grid<T> {
 impl Grid<u8> {
     #[inline]
     pub fn parse(input: &str) -> Self {
-        let raw: Vec<_> = input.lines().map(str::as_bytes).collect();
-        let width = raw[0].len() as i64;
-        let height = raw.len() as i64;
-        let mut bytes = Vec::with_capacity((width * height) as usize);
-        raw.iter().for_each(|slice| bytes.extend_from_slice(slice));
-        Grid {
+        Self::parse_with(input, |b| b)
+    }
+
+    pub fn print(&self, focus: Option<Point>) {
+        println!(
+            "{}",
+            self.display_with(|&b| b as char).highlight(focus, '@')
+        );
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses every byte of the input into a cell. Panics if rows differ in width.
+    pub fn parse_with(input: &str, mut f: impl FnMut(u8) -> T) -> Self {
+        Self::try_parse_with(input, |b| Ok::<T, Infallible>(f(b))).unwrap_or_else(|e| match e {
+            GridFromStrError::Cell(e) => match e {},
+            e => panic!("{e}"),
+        })
+    }
+
+    /// Parses every byte of the input into a cell, stopping at the first error.
+    /// Fails if a row is wider or narrower than the first.
+    pub fn try_parse_with<E>(
+        input: &str,
+        mut f: impl FnMut(u8) -> Result<T, E>,
+    ) -> Result<Self, GridFromStrError<E>> {
+        let mut lines = input.lines().peekable();
+        let width = lines.peek().map_or(0, |l| l.len()) as i64;
+        let mut bytes = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for line in lines {
+            if line.len() as i64 != width {
+                return Err(GridFromStrError::Ragged {
+                    row: height,
+                    width: line.len() as i64,
+                    expected: width,
+                });
+            }
+            for &b in line.as_bytes() {
+                bytes.push(f(b).map_err(GridFromStrError::Cell)?);
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
             width,
             height,
             bytes,
-        }
+        })
     }
 
-    pub fn print(&self, focus: Option<Point>) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let point = Point::new(x, y);
+    #[inline]
+    pub fn in_bounds(&self, p: &Point) -> bool {
+        p.x >= 0 && p.y >= 0 && p.x < self.width && p.y < self.height
+    }
 
-                if let Some(focus) = focus
-                    && focus == point
-                {
-                    print!("@");
-                    continue;
-                }
-                print!("{}", self[point] as char);
-            }
-            println!();
+    #[inline]
+    pub fn orthogonal_neighbors(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbors_by(point, [RIGHT, DOWN, LEFT, UP])
+    }
+
+    /// All 8 neighbors within bounds, in reading order.
+    #[inline]
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbors_by(point, DIAGONAL)
+    }
+
+    /// Neighbors at custom offsets that are within bounds.
+    #[inline]
+    pub fn neighbors_by<const N: usize>(
+        &self,
+        point: Point,
+        offsets: [Point; N],
+    ) -> impl Iterator<Item = Point> {
+        point
+            .neighbors_by(offsets)
+            .into_iter()
+            .filter(|p| self.in_bounds(p))
+    }
+
+    #[inline]
+    pub fn orthogonal_cells(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_by(point, [RIGHT, DOWN, LEFT, UP])
+    }
+
+    /// All 8 neighbors within bounds with their cells, in reading order.
+    #[inline]
+    pub fn neighbor_cells(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_by(point, DIAGONAL)
+    }
+
+    /// Neighbors at custom offsets that are within bounds, with their cells.
+    #[inline]
+    pub fn cells_by<const N: usize>(
+        &self,
+        point: Point,
+        offsets: [Point; N],
+    ) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors_by(point, offsets).map(|p| (p, &self[p]))
+    }
+
+    /// All points in reading order.
+    #[inline]
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// All points with their cells, in reading order.
+    #[inline]
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.bytes)
+    }
+
+    /// The first point in reading order whose cell matches the predicate.
+    pub fn find_by(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells().find(|(_, c)| predicate(c)).map(|(p, _)| p)
+    }
+
+    pub fn find_all_by(
+        &self,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = Point> {
+        self.cells()
+            .filter(move |(_, c)| predicate(c))
+            .map(|(p, _)| p)
+    }
+
+    #[inline]
+    pub fn row(&self, y: i64) -> &[T] {
+        let start = (self.width * y) as usize;
+        &self.bytes[start..start + self.width as usize]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, y: i64) -> &mut [T] {
+        let start = (self.width * y) as usize;
+        &mut self.bytes[start..start + self.width as usize]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    #[inline]
+    pub fn column(&self, x: i64) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[Point::new(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// A view of the `width` by `height` cells starting at `origin`, which must lie within the
+    /// grid.
+    pub fn view(&self, origin: Point, width: i64, height: i64) -> GridView<'_, T> {
+        assert!(
+            width >= 0
+                && height >= 0
+                && origin.x >= 0
+                && origin.y >= 0
+                && origin.x + width <= self.width
+                && origin.y + height <= self.height,
+            "view of {width}x{height} at {origin} exceeds grid of {}x{}",
+            self.width,
+            self.height
+        );
+
+        GridView {
+            grid: self,
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// Displays the grid with a character for every cell, e.g.
+    /// `grid.display_with(|c| if *c { '#' } else { '.' }).highlight(start, 'S')`.
+    pub fn display_with<F: Fn(&T) -> char>(&self, f: F) -> GridDisplay<'_, T, F> {
+        GridDisplay {
+            grid: self,
+            to_char: f,
+            highlights: vec![],
         }
-        println!();
     }
 }
 
@@ -52,18 +213,71 @@ impl<T: Clone> Grid<T> {
             bytes,
         }
     }
+}
 
-    pub fn in_bounds(&self, p: &Point) -> bool {
-        p.x >= 0 && p.y >= 0 && p.x < self.width && p.y < self.height
+impl<T: PartialEq> Grid<T> {
+    /// The first point in reading order that holds `value`.
+    pub fn find(&self, value: &T) -> Option<Point> {
+        self.find_by(|c| c == value)
     }
 
-    #[inline]
-    pub fn orthogonal_neighbors(&self, point: Point) -> Vec<Point> {
-        point
-            .orthogonal_neighbors()
-            .into_iter()
-            .filter(|p| self.in_bounds(p))
-            .collect()
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a {
+        self.find_all_by(move |c| c == value)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Builds a `width` by `height` grid, taking every cell from `source(point)` in this grid.
+    fn remap(&self, width: i64, height: i64, source: impl Fn(Point) -> Point) -> Self {
+        let bytes = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(|p| self[source(p)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            bytes,
+        }
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height;
+        self.remap(self.height, self.width, |p| {
+            Point::new(p.y, height - 1 - p.x)
+        })
+    }
+
+    #[must_use]
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, self.width, |p| {
+            Point::new(width - 1 - p.y, p.x)
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.remap(self.width, self.height, |p| {
+            Point::new(width - 1 - p.x, p.y)
+        })
+    }
+
+    /// Mirrors the grid top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.remap(self.width, self.height, |p| {
+            Point::new(p.x, height - 1 - p.y)
+        })
     }
 }
 
@@ -82,3 +296,332 @@ impl<T> IndexMut<Point> for Grid<T> {
         &mut self.bytes[(self.width * index.y + index.x) as usize]
     }
 }
+
+/// A rectangular window into a grid, indexed relative to its origin.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Point,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    /// Position of the view's top-left cell in the underlying grid.
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, p: &Point) -> bool {
+        p.x >= 0 && p.y >= 0 && p.x < self.width && p.y < self.height
+    }
+
+    /// All points of the view in reading order, relative to its origin.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &'a T)> + use<'a, T> {
+        let view = *self;
+        self.points().map(move |p| (p, &view.grid[view.origin + p]))
+    }
+
+    #[inline]
+    pub fn row(&self, y: i64) -> &'a [T] {
+        let row = self.grid.row(self.origin.y + y);
+        &row[self.origin.x as usize..(self.origin.x + self.width) as usize]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + use<'a, T> {
+        let view = *self;
+        (0..self.height).map(move |y| view.row(y))
+    }
+
+    /// A view within this view, `origin` is relative to this view.
+    pub fn view(&self, origin: Point, width: i64, height: i64) -> GridView<'a, T> {
+        assert!(
+            width >= 0
+                && height >= 0
+                && origin.x >= 0
+                && origin.y >= 0
+                && origin.x + width <= self.width
+                && origin.y + height <= self.height,
+            "view of {width}x{height} at {origin} exceeds view of {}x{}",
+            self.width,
+            self.height
+        );
+
+        GridView {
+            grid: self.grid,
+            origin: self.origin + origin,
+            width,
+            height,
+        }
+    }
+
+    /// Copies the cells of the view into a new grid.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            bytes: self.rows().flat_map(|row| row.iter().cloned()).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for GridView<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        assert!(self.in_bounds(&index), "{index} is outside of the view");
+        &self.grid[self.origin + index]
+    }
+}
+
+/// Parses cells with their `TryFrom<u8>` implementation, e.g. for enums of tiles.
+impl<T: TryFrom<u8>> FromStr for Grid<T> {
+    type Err = GridFromStrError<T::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse_with(s, T::try_from)
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridFromStrError<E> {
+    /// A byte could not be parsed into a cell.
+    Cell(E),
+    /// Row `row` has `width` cells, while the first row has `expected`.
+    Ragged { row: i64, width: i64, expected: i64 },
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for GridFromStrError<E> {}
+
+impl<E: fmt::Display> fmt::Display for GridFromStrError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridFromStrError::Cell(e) => write!(f, "invalid cell: {e}"),
+            GridFromStrError::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "row {row} has {width} cells, expected {expected} like the first row"
+            ),
+        }
+    }
+}
+
+/// Writes one line per row, so that cells which convert back and forth round-trip through
+/// [`FromStr`].
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(|&c| c.into()).fmt(f)
+    }
+}
+
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    to_char: F,
+    highlights: Vec<(Point, char)>,
+}
+
+impl<T, F> GridDisplay<'_, T, F> {
+    /// Shows the given points as `c` instead of their cells.
+    #[must_use]
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, c: char) -> Self {
+        self.highlights.extend(points.into_iter().map(|p| (p, c)));
+        self
+    }
+}
+
+impl<T, F: Fn(&T) -> char> fmt::Display for GridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.grid.height {
+            for x in 0..self.grid.width {
+                let point = Point::new(x, y);
+                // NOTE: later highlights win
+                let c = match self.highlights.iter().rev().find(|(p, _)| *p == point) {
+                    Some((_, c)) => *c,
+                    None => (self.to_char)(&self.grid[point]),
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridFromStrError};
+    use crate::utils::point::Point;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Tile {
+        Wall,
+        Open,
+    }
+
+    impl TryFrom<u8> for Tile {
+        type Error = u8;
+
+        fn try_from(b: u8) -> Result<Self, Self::Error> {
+            match b {
+                b'#' => Ok(Tile::Wall),
+                b'.' => Ok(Tile::Open),
+                _ => Err(b),
+            }
+        }
+    }
+
+    impl From<Tile> for char {
+        fn from(tile: Tile) -> Self {
+            match tile {
+                Tile::Wall => '#',
+                Tile::Open => '.',
+            }
+        }
+    }
+
+    #[test]
+    fn parses_with_functions() {
+        let grid = Grid::parse_with("12\n34\n56\n", |b| b - b'0');
+        assert_eq!((grid.width, grid.height), (2, 3));
+        assert_eq!(grid.bytes, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid[Point::new(1, 2)], 6);
+
+        let grid = Grid::try_parse_with("12\n3x\n", |b| (b as char).to_digit(10).ok_or(b));
+        assert_eq!(grid, Err(GridFromStrError::Cell(b'x')));
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_eq!(
+            "#.#\n.#\n".parse::<Grid<Tile>>(),
+            Err(GridFromStrError::Ragged {
+                row: 1,
+                width: 2,
+                expected: 3
+            })
+        );
+        assert_eq!(
+            "#.\n.#.\n".parse::<Grid<Tile>>().unwrap_err().to_string(),
+            "row 1 has 3 cells, expected 2 like the first row"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 has 1 cells")]
+    fn panics_on_ragged_rows() {
+        Grid::parse("ab\ncd\ne\n");
+    }
+
+    #[test]
+    fn round_trips_through_strings() {
+        let input = "#..#\n.##.\n";
+        let grid: Grid<Tile> = input.parse().unwrap();
+        assert_eq!(grid[Point::new(1, 0)], Tile::Open);
+        assert_eq!(grid[Point::new(1, 1)], Tile::Wall);
+        assert_eq!(grid.to_string(), input);
+
+        let empty: Grid<Tile> = "".parse().unwrap();
+        assert_eq!((empty.width, empty.height), (0, 0));
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    fn highlights_points() {
+        let grid = Grid::parse("...\n...\n");
+        let display = grid
+            .display_with(|&b| b as char)
+            .highlight([Point::new(0, 0), Point::new(2, 1)], 'x')
+            .highlight(Some(Point::new(2, 1)), 'o');
+        // later highlights win.
+        assert_eq!(display.to_string(), "x..\n..o\n");
+
+        grid.print(Some(Point::new(1, 1)));
+        grid.print(None);
+    }
+
+    #[test]
+    fn transforms_non_square_grids() {
+        let grid = Grid::parse("abc\ndef\n");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+
+        let rotated = grid.rotate_clockwise();
+        assert_eq!((rotated.width, rotated.height), (2, 3));
+        assert_eq!(rotated.rotate_counter_clockwise(), grid);
+        assert_eq!(
+            rotated
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn reads_columns() {
+        let grid = Grid::parse("abc\ndef\n");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        let columns: Vec<Vec<u8>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [b"ad", b"be", b"cf"]);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"abc", b"def"]);
+    }
+
+    #[test]
+    fn views_nested_windows() {
+        let grid = Grid::parse("abcd\nefgh\nijkl\n");
+        let view = grid.view(Point::new(1, 1), 3, 2);
+        assert_eq!(view.to_grid().to_string(), "fgh\njkl\n");
+        assert_eq!(view[Point::new(2, 0)], b'h');
+
+        let nested = view.view(Point::new(1, 0), 2, 2);
+        assert_eq!(nested.origin(), Point::new(2, 1));
+        assert_eq!(nested.to_grid().to_string(), "gh\nkl\n");
+        assert_eq!(nested[Point::new(0, 1)], b'k');
+        assert_eq!(nested.cells().map(|(_, c)| *c).collect::<Vec<_>>(), b"ghkl");
+
+        let empty = grid.view(Point::new(4, 3), 0, 0);
+        assert_eq!(empty.to_grid().bytes, []);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the view")]
+    fn panics_outside_of_views() {
+        // (2, 0) of the view is `d` in the grid, which is next to the view.
+        let grid = Grid::parse("abcd\nefgh\n");
+        let _ = grid.view(Point::new(1, 0), 2, 2)[Point::new(2, 0)];
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds view")]
+    fn rejects_nested_views_past_the_parent() {
+        let grid = Grid::parse("abcd\nefgh\n");
+        grid.view(Point::new(1, 0), 2, 2)
+            .view(Point::new(1, 0), 2, 1);
+    }
+}
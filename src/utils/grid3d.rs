@@ -1,4 +1,6 @@
-use crate::utils::point3d::Point3D;
+use crate::utils::point3d::{
+    NEIGHBORS_18, NEIGHBORS_26, ORTHOGONAL, Point3D, get_point3d_vec_bounds,
+};
 use std::ops::{Index, IndexMut};
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -9,9 +11,9 @@ pub struct Grid3D<T> {
     pub bytes: Vec<T>,
 }
 
-impl Grid3D<bool> {
-    pub fn new(width: i64, height: i64, depth: i64) -> Self {
-        let bytes = vec![false; (width * height * depth) as usize];
+impl<T: Clone> Grid3D<T> {
+    pub fn new(width: i64, height: i64, depth: i64, seed: T) -> Self {
+        let bytes = vec![seed; (width * height * depth) as usize];
 
         Grid3D {
             width,
@@ -20,6 +22,29 @@ impl Grid3D<bool> {
             bytes,
         }
     }
+}
+
+impl Grid3D<bool> {
+    /// Marks the given points in a grid that fits them with `padding` empty cells on every
+    /// side. Returns the grid and the offset to subtract from points to index it.
+    pub fn from_points(points: impl IntoIterator<Item = Point3D>, padding: i64) -> (Self, Point3D) {
+        let points: Vec<Point3D> = points.into_iter().collect();
+        if points.is_empty() {
+            return (Grid3D::new(0, 0, 0, false), Point3D::new(0, 0, 0));
+        }
+
+        let (min, max) = get_point3d_vec_bounds(&points);
+        let padding = Point3D::new(padding, padding, padding);
+        let offset = min - padding;
+        let size = max - min + padding * 2 + Point3D::new(1, 1, 1);
+
+        let mut grid = Grid3D::new(size.x, size.y, size.z, false);
+        for point in points {
+            grid[point - offset] = true;
+        }
+
+        (grid, offset)
+    }
 
     pub fn print_grid3d(&self) {
         let w = self.width as usize;
@@ -84,19 +109,120 @@ impl Grid3D<bool> {
 
 impl<T> Grid3D<T> {
     #[inline]
-    pub fn orthogonal_neighbors(&self, point: &Point3D) -> Vec<Point3D> {
+    pub fn in_bounds(&self, p: &Point3D) -> bool {
+        p.x >= 0
+            && p.y >= 0
+            && p.z >= 0
+            && p.x < self.width
+            && p.y < self.height
+            && p.z < self.depth
+    }
+
+    /// All points, x varying fastest.
+    pub fn points(&self) -> impl Iterator<Item = Point3D> + use<T> {
+        let (width, height) = (self.width, self.height);
+        (0..self.depth).flat_map(move |z| {
+            (0..height).flat_map(move |y| (0..width).map(move |x| Point3D::new(x, y, z)))
+        })
+    }
+
+    /// The 6 neighbors sharing a face that are within bounds.
+    #[inline]
+    pub fn orthogonal_neighbors(&self, point: &Point3D) -> impl Iterator<Item = Point3D> {
+        self.neighbors_by(point, ORTHOGONAL)
+    }
+
+    /// The 18 neighbors sharing a face or an edge that are within bounds.
+    #[inline]
+    pub fn neighbors_18(&self, point: &Point3D) -> impl Iterator<Item = Point3D> {
+        self.neighbors_by(point, NEIGHBORS_18)
+    }
+
+    /// The 26 neighbors sharing a face, an edge or a corner that are within bounds.
+    #[inline]
+    pub fn neighbors_26(&self, point: &Point3D) -> impl Iterator<Item = Point3D> {
+        self.neighbors_by(point, NEIGHBORS_26)
+    }
+
+    /// Neighbors at custom offsets that are within bounds.
+    #[inline]
+    pub fn neighbors_by<const N: usize>(
+        &self,
+        point: &Point3D,
+        offsets: [Point3D; N],
+    ) -> impl Iterator<Item = Point3D> {
         point
-            .orthogonal_neighbors()
+            .neighbors_by(offsets)
             .into_iter()
+            .filter(|p| self.in_bounds(p))
+    }
+
+    /// Marks the cells that are not `solid` and connect to the outside of the grid through
+    /// faces, as opposed to air pockets that are enclosed.
+    pub fn exterior(&self, mut solid: impl FnMut(&T) -> bool) -> Grid3D<bool> {
+        let mut exterior = Grid3D::new(self.width, self.height, self.depth, false);
+        let mut todo: Vec<Point3D> = self
+            .points()
             .filter(|p| {
-                p.x >= 0
-                    && p.y >= 0
-                    && p.z >= 0
-                    && p.x < self.width
-                    && p.y < self.height
-                    && p.z < self.depth
+                p.x == 0
+                    || p.y == 0
+                    || p.z == 0
+                    || p.x == self.width - 1
+                    || p.y == self.height - 1
+                    || p.z == self.depth - 1
             })
-            .collect()
+            .filter(|p| !solid(&self[*p]))
+            .collect();
+
+        for point in &todo {
+            exterior[*point] = true;
+        }
+
+        while let Some(point) = todo.pop() {
+            for neighbor in self.orthogonal_neighbors(&point) {
+                if !exterior[neighbor] && !solid(&self[neighbor]) {
+                    exterior[neighbor] = true;
+                    todo.push(neighbor);
+                }
+            }
+        }
+
+        exterior
+    }
+
+    /// Number of faces of `solid` cells that do not touch another solid cell, including those
+    /// facing enclosed air pockets.
+    pub fn surface_area(&self, mut solid: impl FnMut(&T) -> bool) -> usize {
+        self.count_faces(&mut solid, |_| true)
+    }
+
+    /// Number of faces of `solid` cells that touch the outside, see [`Grid3D::exterior`].
+    pub fn exterior_surface_area(&self, mut solid: impl FnMut(&T) -> bool) -> usize {
+        let exterior = self.exterior(&mut solid);
+        self.count_faces(&mut solid, |p| exterior[p])
+    }
+
+    /// Faces of solid cells towards cells that are out of bounds or not solid and `open`.
+    fn count_faces(
+        &self,
+        solid: &mut impl FnMut(&T) -> bool,
+        mut open: impl FnMut(Point3D) -> bool,
+    ) -> usize {
+        let mut faces = 0;
+
+        for point in self.points() {
+            if !solid(&self[point]) {
+                continue;
+            }
+
+            for neighbor in point.orthogonal_neighbors() {
+                if !self.in_bounds(&neighbor) || (!solid(&self[neighbor]) && open(neighbor)) {
+                    faces += 1;
+                }
+            }
+        }
+
+        faces
     }
 }
 
@@ -117,3 +243,78 @@ impl<T> IndexMut<Point3D> for Grid3D<T> {
         &mut self.bytes[idx]
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid3D;
+    use crate::utils::point3d::Point3D;
+
+    const DROPLET: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    fn cubes(input: &str) -> Vec<Point3D> {
+        input
+            .lines()
+            .map(|line| {
+                let [x, y, z] = [0, 1, 2].map(|i| line.split(',').nth(i).unwrap().parse().unwrap());
+                Point3D::new(x, y, z)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn builds_grids_from_points() {
+        let points = [Point3D::new(1, 5, -2), Point3D::new(3, 5, 0)];
+        let (grid, offset) = Grid3D::from_points(points, 1);
+        assert_eq!(offset, Point3D::new(0, 4, -3));
+        assert_eq!((grid.width, grid.height, grid.depth), (5, 3, 5));
+        assert_eq!(grid.bytes.iter().filter(|c| **c).count(), 2);
+        assert!(points.iter().all(|p| grid[*p - offset]));
+
+        let (grid, offset) = Grid3D::from_points(points, 0);
+        assert_eq!(offset, Point3D::new(1, 5, -2));
+        assert_eq!((grid.width, grid.height, grid.depth), (3, 1, 3));
+
+        let (grid, offset) = Grid3D::from_points([], 1);
+        assert_eq!(offset, Point3D::new(0, 0, 0));
+        assert!(grid.bytes.is_empty());
+    }
+
+    #[test]
+    fn measures_surface_area() {
+        let (grid, _) = Grid3D::from_points(cubes("1,1,1\n2,1,1\n"), 1);
+        assert_eq!(grid.surface_area(|c| *c), 10);
+        assert_eq!(grid.exterior_surface_area(|c| *c), 10);
+
+        for padding in [0, 1] {
+            let (grid, _) = Grid3D::from_points(cubes(DROPLET), padding);
+            assert_eq!(grid.surface_area(|c| *c), 64);
+            assert_eq!(grid.exterior_surface_area(|c| *c), 58);
+        }
+    }
+
+    #[test]
+    fn finds_enclosed_air() {
+        let (grid, offset) = Grid3D::from_points(cubes(DROPLET), 1);
+        let exterior = grid.exterior(|c| *c);
+        assert!(!exterior[Point3D::new(2, 2, 5) - offset]);
+        assert!(exterior[Point3D::new(0, 0, 0)]);
+        assert_eq!(
+            grid.points().filter(|p| !grid[*p] && !exterior[*p]).count(),
+            1
+        );
+    }
+}
@@ -1,7 +1,13 @@
+pub mod automaton;
+pub mod bit_grid;
 pub mod grid;
 pub mod grid3d;
 pub mod misc;
+pub mod padded_grid;
 pub mod parse;
 pub mod point;
 pub mod point3d;
 pub mod range;
+pub mod region;
+pub mod search;
+pub mod sparse_grid;
@@ -0,0 +1,214 @@
+use crate::utils::grid::Grid;
+use crate::utils::point::{DIAGONAL, DOWN, LEFT, Point, RIGHT, UP};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A grid surrounded by a border of `padding` sentinel cells on every side.
+///
+/// Points use the coordinates of the unpadded grid, so neighbors up to `padding` cells away
+/// from any point in bounds can be indexed without bounds checks. Leaving the border is only
+/// caught in debug builds: release builds read a cell of a neighboring row instead, or panic
+/// past either end of the grid.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PaddedGrid<T> {
+    pub width: i64,
+    pub height: i64,
+    pub padding: i64,
+    grid: Grid<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A copy of the grid with a border of `padding` cells of `sentinel`.
+    pub fn padded(&self, padding: i64, sentinel: T) -> PaddedGrid<T> {
+        let mut grid = Grid::new(
+            self.width + 2 * padding,
+            self.height + 2 * padding,
+            sentinel,
+        );
+        let offset = Point::new(padding, padding);
+        for (point, cell) in self.cells() {
+            grid[point + offset] = cell.clone();
+        }
+
+        PaddedGrid {
+            width: self.width,
+            height: self.height,
+            padding,
+            grid,
+        }
+    }
+}
+
+impl<T> PaddedGrid<T> {
+    #[inline]
+    fn offset(&self) -> Point {
+        Point::new(self.padding, self.padding)
+    }
+
+    /// Whether `p` lies within the unpadded grid.
+    #[inline]
+    pub fn in_bounds(&self, p: &Point) -> bool {
+        p.x >= 0 && p.y >= 0 && p.x < self.width && p.y < self.height
+    }
+
+    /// Whether `p` lies within the unpadded grid or its border.
+    #[inline]
+    pub fn in_padding(&self, p: &Point) -> bool {
+        self.grid.in_bounds(&(*p + self.offset()))
+    }
+
+    /// All points of the unpadded grid in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// All points of the unpadded grid with their cells, in reading order.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().map(|p| (p, &self[p]))
+    }
+
+    pub fn find_by(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells().find(|(_, c)| predicate(c)).map(|(p, _)| p)
+    }
+
+    pub fn find_all_by(
+        &self,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = Point> {
+        self.cells()
+            .filter(move |(_, c)| predicate(c))
+            .map(|(p, _)| p)
+    }
+
+    /// Row `y` of the unpadded grid.
+    #[inline]
+    pub fn row(&self, y: i64) -> &[T] {
+        let start = self.padding as usize;
+        &self.grid.row(y + self.padding)[start..start + self.width as usize]
+    }
+
+    /// All 4 orthogonal neighbors, including sentinel cells.
+    #[inline]
+    pub fn orthogonal_cells(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_by(point, [RIGHT, DOWN, LEFT, UP])
+    }
+
+    /// All 8 neighbors in reading order, including sentinel cells.
+    #[inline]
+    pub fn neighbor_cells(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_by(point, DIAGONAL)
+    }
+
+    /// Neighbors at custom offsets with their cells, including sentinel cells.
+    #[inline]
+    pub fn cells_by<const N: usize>(
+        &self,
+        point: Point,
+        offsets: [Point; N],
+    ) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .neighbors_by(offsets)
+            .into_iter()
+            .map(|p| (p, &self[p]))
+    }
+
+    /// The grid including its border, where the unpadded grid starts at `(padding, padding)`.
+    pub fn as_padded(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// A copy of the grid without its border.
+    pub fn unpadded(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.grid
+            .view(self.offset(), self.width, self.height)
+            .to_grid()
+    }
+}
+
+impl<T: PartialEq> PaddedGrid<T> {
+    pub fn find(&self, value: &T) -> Option<Point> {
+        self.find_by(|c| c == value)
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a {
+        self.find_all_by(move |c| c == value)
+    }
+}
+
+impl<T> Index<Point> for PaddedGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        debug_assert!(self.in_padding(&index), "{index} is outside of the padding");
+        &self.grid[index + self.offset()]
+    }
+}
+
+impl<T> IndexMut<Point> for PaddedGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        debug_assert!(self.in_padding(&index), "{index} is outside of the padding");
+        let offset = self.offset();
+        &mut self.grid[index + offset]
+    }
+}
+
+/// Writes the unpadded grid, see [`Grid`]'s `Display`.
+impl<T: Copy + Into<char>> fmt::Display for PaddedGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for &c in self.row(y) {
+                write!(f, "{}", c.into())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::utils::grid::Grid;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn indexes_unpadded_coordinates() {
+        let grid: Grid<u8> = Grid::parse("ab\ncd\n");
+        let mut padded = grid.padded(1, b'#');
+        assert_eq!(padded[Point::new(1, 0)], b'b');
+        assert_eq!(padded[Point::new(-1, -1)], b'#');
+        assert_eq!(padded[Point::new(2, 2)], b'#');
+        assert_eq!(padded.row(1), b"cd");
+
+        padded[Point::new(0, 1)] = b'x';
+        assert_eq!(padded.to_string(), "ab\nxd\n");
+        assert_eq!(padded.as_padded().to_string(), "####\n#ab#\n#xd#\n####\n");
+        assert_eq!(padded.unpadded(), Grid::parse("ab\nxd\n"));
+        assert_eq!(padded.find(&b'x'), Some(Point::new(0, 1)));
+    }
+
+    #[test]
+    fn sees_sentinels_as_neighbors() {
+        let padded = Grid::parse("a\n").padded(1, b'#');
+        assert!(
+            padded
+                .neighbor_cells(Point::new(0, 0))
+                .all(|(_, c)| *c == b'#')
+        );
+        assert_eq!(padded.orthogonal_cells(Point::new(0, 0)).count(), 4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "outside of the padding")]
+    fn panics_outside_of_padding() {
+        // in release builds, (3, 0) wraps to the start of the next row.
+        let padded = Grid::parse("ab\ncd\n").padded(1, b'#');
+        let _ = padded[Point::new(3, 0)];
+    }
+}
@@ -41,13 +41,20 @@ impl Point {
     }
 
     #[inline]
-    pub fn orthogonal_neighbors(self) -> Vec<Self> {
-        vec![
-            Point::new(self.x + 1, self.y),
-            Point::new(self.x, self.y + 1),
-            Point::new(self.x - 1, self.y),
-            Point::new(self.x, self.y - 1),
-        ]
+    pub fn orthogonal_neighbors(self) -> [Self; 4] {
+        self.neighbors_by([RIGHT, DOWN, LEFT, UP])
+    }
+
+    /// All 8 neighbors in reading order.
+    #[inline]
+    pub fn neighbors(self) -> [Self; 8] {
+        self.neighbors_by(DIAGONAL)
+    }
+
+    /// Neighbors at custom offsets, e.g. knight moves.
+    #[inline]
+    pub fn neighbors_by<const N: usize>(self, offsets: [Point; N]) -> [Self; N] {
+        offsets.map(|offset| self + offset)
     }
 
     #[inline]
@@ -10,6 +10,37 @@ pub struct Point3D {
 }
 
 pub const ORIGIN: Point3D = Point3D::new(0, 0, 0);
+/// Neighbors sharing a face
+pub const ORTHOGONAL: [Point3D; 6] = offsets(1);
+/// Neighbors sharing a face or an edge
+pub const NEIGHBORS_18: [Point3D; 18] = offsets(2);
+/// Neighbors sharing a face, an edge or a corner
+pub const NEIGHBORS_26: [Point3D; 26] = offsets(3);
+
+/// All offsets in `-1..=1` with between 1 and `max_axes` non-zero coordinates, z-major.
+const fn offsets<const N: usize>(max_axes: i64) -> [Point3D; N] {
+    let mut offsets = [ORIGIN; N];
+    let mut i = 0;
+    let mut z: i64 = -1;
+    while z <= 1 {
+        let mut y: i64 = -1;
+        while y <= 1 {
+            let mut x: i64 = -1;
+            while x <= 1 {
+                let axes = x.abs() + y.abs() + z.abs();
+                if axes >= 1 && axes <= max_axes {
+                    offsets[i] = Point3D::new(x, y, z);
+                    i += 1;
+                }
+                x += 1;
+            }
+            y += 1;
+        }
+        z += 1;
+    }
+    assert!(i == N);
+    offsets
+}
 
 impl Point3D {
     #[inline]
@@ -18,15 +49,14 @@ impl Point3D {
     }
 
     #[inline]
-    pub fn orthogonal_neighbors(self) -> Vec<Self> {
-        vec![
-            Point3D::new(self.x + 1, self.y, self.z),
-            Point3D::new(self.x, self.y + 1, self.z),
-            Point3D::new(self.x - 1, self.y, self.z),
-            Point3D::new(self.x, self.y - 1, self.z),
-            Point3D::new(self.x, self.y, self.z + 1),
-            Point3D::new(self.x, self.y, self.z - 1),
-        ]
+    pub fn orthogonal_neighbors(self) -> [Self; 6] {
+        self.neighbors_by(ORTHOGONAL)
+    }
+
+    /// Neighbors at custom offsets, e.g. [`NEIGHBORS_26`].
+    #[inline]
+    pub fn neighbors_by<const N: usize>(self, offsets: [Point3D; N]) -> [Self; N] {
+        offsets.map(|offset| self + offset)
     }
 
     #[inline]
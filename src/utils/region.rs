@@ -0,0 +1,243 @@
+use crate::utils::grid::Grid;
+use crate::utils::point::{DIAGONAL, DOWN, LEFT, ORTHOGONAL, Point, RIGHT, UP};
+use std::collections::VecDeque;
+
+const UNLABELED: u32 = u32::MAX;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Connectivity {
+    /// Orthogonal neighbors only.
+    Four,
+    /// Orthogonal and diagonal neighbors.
+    Eight,
+}
+
+impl Connectivity {
+    #[inline]
+    pub fn offsets(self) -> &'static [Point] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL,
+            Connectivity::Eight => &DIAGONAL,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Region {
+    pub label: u32,
+    /// The first point of the region in reading order.
+    pub seed: Point,
+    pub area: usize,
+    /// Number of cell edges between the region and other regions or the outside.
+    pub perimeter: usize,
+    /// Number of straight fence segments around the region, including those of holes.
+    pub sides: usize,
+    pub touches_border: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Regions {
+    /// The label of every cell, an index into `regions`.
+    pub labels: Grid<u32>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    #[inline]
+    pub fn region_of(&self, point: Point) -> &Region {
+        &self.regions[self.labels[point] as usize]
+    }
+
+    /// Regions that do not touch the border of the grid.
+    pub fn enclosed(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter().filter(|r| !r.touches_border)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Points reachable from `seed`, in breadth-first order. A step from one cell to a neighbor
+    /// is taken if `connected(from, to)` holds.
+    pub fn flood_fill(
+        &self,
+        seed: Point,
+        connectivity: Connectivity,
+        mut connected: impl FnMut(&T, &T) -> bool,
+    ) -> Vec<Point> {
+        let mut visited = Grid::new(self.width, self.height, false);
+        let mut filled = vec![seed];
+        let mut next = 0;
+        visited[seed] = true;
+
+        while let Some(&point) = filled.get(next) {
+            next += 1;
+
+            for offset in connectivity.offsets() {
+                let neighbor = point + *offset;
+                if self.in_bounds(&neighbor)
+                    && !visited[neighbor]
+                    && connected(&self[point], &self[neighbor])
+                {
+                    visited[neighbor] = true;
+                    filled.push(neighbor);
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// Splits the whole grid into connected regions, see [`Grid::flood_fill`].
+    /// Labels are assigned in reading order of the regions' first cells.
+    ///
+    /// Perimeter and sides always count the orthogonal cell edges of a region, so with
+    /// [`Connectivity::Eight`] cells that only touch diagonally are fenced separately.
+    pub fn label_regions(
+        &self,
+        connectivity: Connectivity,
+        mut connected: impl FnMut(&T, &T) -> bool,
+    ) -> Regions {
+        let mut labels = Grid::new(self.width, self.height, UNLABELED);
+        let mut regions = vec![];
+        let mut todo = VecDeque::new();
+
+        for seed in self.points() {
+            if labels[seed] != UNLABELED {
+                continue;
+            }
+
+            let label = regions.len() as u32;
+            let mut area = 0;
+            labels[seed] = label;
+            todo.push_back(seed);
+
+            while let Some(point) = todo.pop_front() {
+                area += 1;
+
+                for offset in connectivity.offsets() {
+                    let neighbor = point + *offset;
+                    if self.in_bounds(&neighbor)
+                        && labels[neighbor] == UNLABELED
+                        && connected(&self[point], &self[neighbor])
+                    {
+                        labels[neighbor] = label;
+                        todo.push_back(neighbor);
+                    }
+                }
+            }
+
+            regions.push(Region {
+                label,
+                seed,
+                area,
+                perimeter: 0,
+                sides: 0,
+                touches_border: false,
+            });
+        }
+
+        for (point, &label) in labels.cells() {
+            let same = |offset: Point| {
+                let neighbor = point + offset;
+                labels.in_bounds(&neighbor) && labels[neighbor] == label
+            };
+            let region = &mut regions[label as usize];
+
+            region.perimeter += ORTHOGONAL.iter().filter(|d| !same(**d)).count();
+            region.touches_border |= point.x == 0
+                || point.y == 0
+                || point.x == labels.width - 1
+                || point.y == labels.height - 1;
+
+            // Every corner of a region starts a new side
+            for (a, b) in [(UP, RIGHT), (RIGHT, DOWN), (DOWN, LEFT), (LEFT, UP)] {
+                let convex = !same(a) && !same(b);
+                let concave = same(a) && same(b) && !same(a + b);
+                if convex || concave {
+                    region.sides += 1;
+                }
+            }
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Connectivity;
+    use crate::utils::grid::Grid;
+    use crate::utils::point::Point;
+
+    /// Area, perimeter and sides of every region, in label order.
+    fn measure(grid: &str, connectivity: Connectivity) -> Vec<(char, usize, usize, usize)> {
+        let grid = Grid::parse(grid);
+        grid.label_regions(connectivity, |a, b| a == b)
+            .regions
+            .iter()
+            .map(|r| (grid[r.seed] as char, r.area, r.perimeter, r.sides))
+            .collect()
+    }
+
+    #[test]
+    fn measures_regions() {
+        let regions = measure("AAAA\nBBCD\nBBCC\nEEEC\n", Connectivity::Four);
+        assert_eq!(
+            regions,
+            [
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn measures_regions_with_holes() {
+        let regions = measure("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n", Connectivity::Four);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[0], ('O', 21, 36, 20));
+        assert!(regions[1..].iter().all(|r| *r == ('X', 1, 4, 4)));
+    }
+
+    #[test]
+    fn measures_e_shape() {
+        let regions = measure("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE\n", Connectivity::Four);
+        assert_eq!(
+            regions,
+            [('E', 17, 36, 12), ('X', 4, 10, 4), ('X', 4, 10, 4)]
+        );
+    }
+
+    #[test]
+    fn counts_sides_of_diagonally_touching_regions() {
+        let grid = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n";
+        let four = measure(grid, Connectivity::Four);
+        assert_eq!(four, [('A', 28, 40, 12), ('B', 4, 8, 4), ('B', 4, 8, 4)]);
+
+        // fences are still counted per cell edge, so both squares keep their own sides.
+        let eight = measure(grid, Connectivity::Eight);
+        assert_eq!(eight, [('A', 28, 40, 12), ('B', 8, 16, 8)]);
+    }
+
+    #[test]
+    fn tracks_border_contact() {
+        let grid: Grid<u8> = Grid::parse("AAA\nABA\nAAA\n");
+        let regions = grid.label_regions(Connectivity::Four, |a, b| a == b);
+        assert!(regions.region_of(Point::new(0, 0)).touches_border);
+        assert_eq!(
+            regions.enclosed().map(|r| r.seed).collect::<Vec<_>>(),
+            [Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn floods_by_connectivity() {
+        let grid: Grid<u8> = Grid::parse("#.\n.#\n");
+        let four = grid.flood_fill(Point::new(0, 0), Connectivity::Four, |a, b| a == b);
+        let eight = grid.flood_fill(Point::new(0, 0), Connectivity::Eight, |a, b| a == b);
+        assert_eq!(four, [Point::new(0, 0)]);
+        assert_eq!(eight, [Point::new(0, 0), Point::new(1, 1)]);
+    }
+}
@@ -0,0 +1,384 @@
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+use hashbrown::HashMap;
+use hashbrown::hash_map::Entry;
+use pathfinding::prelude::{astar, bfs, dijkstra};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+/// A position with the direction it was entered from and the number of straight steps taken
+/// in that direction, for puzzles that restrict or charge for turning.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct State {
+    pub position: Point,
+    pub direction: Point,
+    pub steps: u32,
+}
+
+impl State {
+    #[inline]
+    pub const fn new(position: Point, direction: Point) -> Self {
+        Self {
+            position,
+            direction,
+            steps: 0,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Orthogonal moves from `point` into cells that `passable(from, to)` allows.
+    #[inline]
+    fn moves<'a>(
+        &'a self,
+        point: Point,
+        passable: &'a mut impl FnMut(&T, &T) -> bool,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.orthogonal_neighbors(point)
+            .filter(move |n| passable(&self[point], &self[*n]))
+    }
+
+    /// Number of orthogonal steps from the nearest source to every cell, `None` if unreachable.
+    pub fn bfs_distances(
+        &self,
+        sources: impl IntoIterator<Item = Point>,
+        mut passable: impl FnMut(&T, &T) -> bool,
+    ) -> Grid<Option<u32>> {
+        let mut distances = Grid::new(self.width, self.height, None);
+        let mut todo = VecDeque::new();
+
+        for source in sources {
+            if distances[source].is_none() {
+                distances[source] = Some(0);
+                todo.push_back(source);
+            }
+        }
+
+        while let Some(point) = todo.pop_front() {
+            let distance = distances[point].unwrap() + 1;
+
+            for next in self.moves(point, &mut passable) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance);
+                    todo.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// A path with the fewest orthogonal steps, including `start` and `goal`.
+    pub fn bfs_path(
+        &self,
+        start: Point,
+        goal: Point,
+        mut passable: impl FnMut(&T, &T) -> bool,
+    ) -> Option<Vec<Point>> {
+        bfs(
+            &start,
+            |p| self.moves(*p, &mut passable).collect::<Vec<_>>(),
+            |p| *p == goal,
+        )
+    }
+
+    /// The cheapest orthogonal path to a point that satisfies `goal`, and its cost.
+    /// `cost(from, to)` is the cost of a move, `None` if it is not allowed.
+    pub fn dijkstra(
+        &self,
+        start: Point,
+        goal: impl FnMut(&Point) -> bool,
+        mut cost: impl FnMut(Point, Point) -> Option<u64>,
+    ) -> Option<(Vec<Point>, u64)> {
+        dijkstra(
+            &start,
+            |p| self.weighted_moves(*p, &mut cost).collect::<Vec<_>>(),
+            goal,
+        )
+    }
+
+    /// Like [`Grid::dijkstra`], where entering a cell costs `cost(cell)`.
+    pub fn dijkstra_cells(
+        &self,
+        start: Point,
+        goal: impl FnMut(&Point) -> bool,
+        mut cost: impl FnMut(&T) -> Option<u64>,
+    ) -> Option<(Vec<Point>, u64)> {
+        self.dijkstra(start, goal, |_, to| cost(&self[to]))
+    }
+
+    /// Like [`Grid::dijkstra`] towards a single goal, guided by the manhattan distance.
+    /// The result is only guaranteed to be optimal if every move costs at least 1.
+    pub fn astar(
+        &self,
+        start: Point,
+        goal: Point,
+        mut cost: impl FnMut(Point, Point) -> Option<u64>,
+    ) -> Option<(Vec<Point>, u64)> {
+        astar(
+            &start,
+            |p| self.weighted_moves(*p, &mut cost).collect::<Vec<_>>(),
+            |p| p.manhattan(goal) as u64,
+            |p| *p == goal,
+        )
+    }
+
+    #[inline]
+    fn weighted_moves<'a>(
+        &'a self,
+        point: Point,
+        cost: &'a mut impl FnMut(Point, Point) -> Option<u64>,
+    ) -> impl Iterator<Item = (Point, u64)> + 'a {
+        self.orthogonal_neighbors(point)
+            .filter_map(move |n| Some((n, cost(point, n)?)))
+    }
+
+    /// Moves from a state straight ahead or after turning left or right, never backwards.
+    #[inline]
+    fn turns<'a>(
+        &'a self,
+        state: State,
+        cost: &'a mut impl FnMut(&State, &State) -> Option<u64>,
+    ) -> impl Iterator<Item = (State, u64)> + 'a {
+        let d = state.direction;
+        [d, d.clockwise(), d.counter_clockwise()]
+            .into_iter()
+            .filter_map(move |direction| {
+                let next = State {
+                    position: state.position + direction,
+                    direction,
+                    steps: if direction == d { state.steps + 1 } else { 1 },
+                };
+                if !self.in_bounds(&next.position) {
+                    return None;
+                }
+                Some((next, cost(&state, &next)?))
+            })
+    }
+
+    /// The cheapest path through position and direction states, starting from any of `starts`.
+    /// `cost(from, to)` is the cost of a move, `None` if it is not allowed, e.g. because the
+    /// number of straight steps exceeds a limit.
+    pub fn dijkstra_directed(
+        &self,
+        starts: impl IntoIterator<Item = State>,
+        mut goal: impl FnMut(&State) -> bool,
+        mut cost: impl FnMut(&State, &State) -> Option<u64>,
+    ) -> Option<(Vec<State>, u64)> {
+        let starts: Vec<State> = starts.into_iter().collect();
+
+        // NOTE: `None` is a virtual source that leads to every start for free.
+        let (path, total) = dijkstra(
+            &None,
+            |state: &Option<State>| match state {
+                None => starts.iter().map(|s| (Some(*s), 0)).collect::<Vec<_>>(),
+                Some(state) => self
+                    .turns(*state, &mut cost)
+                    .map(|(s, c)| (Some(s), c))
+                    .collect(),
+            },
+            |state| state.as_ref().is_some_and(&mut goal),
+        )?;
+
+        Some((path.into_iter().flatten().collect(), total))
+    }
+
+    /// The cost of the cheapest orthogonal paths from `start` to `goal`, and how many there are.
+    pub fn count_shortest_paths(
+        &self,
+        start: Point,
+        goal: Point,
+        mut cost: impl FnMut(Point, Point) -> Option<u64>,
+    ) -> Option<(u64, u64)> {
+        count_shortest_paths(
+            start,
+            |p| self.weighted_moves(*p, &mut cost).collect::<Vec<_>>(),
+            |p| *p == goal,
+        )
+    }
+}
+
+/// The cost of the cheapest paths from `start` to any state that satisfies `success`, and how
+/// many distinct paths share that cost. Costs must be positive, otherwise paths are miscounted.
+pub fn count_shortest_paths<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut success: impl FnMut(&S) -> bool,
+) -> Option<(u64, u64)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut states = vec![start.clone()];
+    let mut indices: HashMap<S, usize> = HashMap::from([(start, 0)]);
+    let mut costs = vec![0];
+    let mut counts = vec![1];
+    let mut heap = BinaryHeap::from([Reverse((0, 0))]);
+    let mut found: Option<(u64, u64)> = None;
+
+    while let Some(Reverse((cost, i))) = heap.pop() {
+        if cost > costs[i] {
+            continue;
+        }
+
+        if let Some((best, _)) = found
+            && cost > best
+        {
+            break;
+        }
+
+        if success(&states[i]) {
+            let (_, count) = found.get_or_insert((cost, 0));
+            *count += counts[i];
+            continue;
+        }
+
+        for (next, step) in successors(&states[i]) {
+            let next_cost = cost + step;
+            let j = match indices.entry(next) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    states.push(entry.key().clone());
+                    costs.push(u64::MAX);
+                    counts.push(0);
+                    *entry.insert(states.len() - 1)
+                }
+            };
+
+            if next_cost < costs[j] {
+                costs[j] = next_cost;
+                counts[j] = counts[i];
+                heap.push(Reverse((next_cost, j)));
+            } else if next_cost == costs[j] {
+                counts[j] += counts[i];
+            }
+        }
+    }
+
+    found
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::State;
+    use crate::utils::grid::Grid;
+    use crate::utils::point::{DOWN, ORIGIN, Point, RIGHT};
+
+    const MAZE: &str = "\
+.....
+.###.
+.#...
+.#.##
+...#.
+";
+
+    const CRUCIBLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    fn open(_: &u8, to: &u8) -> bool {
+        *to != b'#'
+    }
+
+    #[test]
+    fn finds_bfs_distances_and_paths() {
+        let grid: Grid<u8> = Grid::parse(MAZE);
+        let distances = grid.bfs_distances([ORIGIN], open);
+        assert_eq!(distances[Point::new(2, 4)], Some(6));
+        assert_eq!(distances[Point::new(2, 3)], Some(7));
+        assert_eq!(distances[Point::new(1, 1)], None);
+        assert_eq!(distances[Point::new(4, 4)], None);
+
+        let path = grid.bfs_path(ORIGIN, Point::new(2, 3), open).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(path.first(), Some(&ORIGIN));
+        assert_eq!(path.last(), Some(&Point::new(2, 3)));
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let grid: Grid<u8> = Grid::parse("131\n191\n111\n");
+        let cost = |cell: &u8| Some(u64::from(cell - b'0'));
+        let goal = Point::new(2, 2);
+
+        let (path, total) = grid.dijkstra_cells(ORIGIN, |p| *p == goal, cost).unwrap();
+        assert_eq!(total, 4);
+        assert_eq!(path.len(), 5);
+
+        let (_, total) = grid.astar(ORIGIN, goal, |_, to| cost(&grid[to])).unwrap();
+        assert_eq!(total, 4);
+    }
+
+    #[test]
+    fn finds_cheapest_directed_paths() {
+        let grid: Grid<u8> = Grid::parse(CRUCIBLE);
+        let end = Point::new(grid.width - 1, grid.height - 1);
+        let starts = [State::new(ORIGIN, RIGHT), State::new(ORIGIN, DOWN)];
+        let heat = |to: &State| Some(u64::from(grid[to.position] - b'0'));
+
+        let (_, total) = grid
+            .dijkstra_directed(
+                starts,
+                |s| s.position == end,
+                |_, to| if to.steps > 3 { None } else { heat(to) },
+            )
+            .unwrap();
+        assert_eq!(total, 102);
+
+        let (_, total) = grid
+            .dijkstra_directed(
+                starts,
+                |s| s.position == end && s.steps >= 4,
+                |from, to| {
+                    let early_turn = to.direction != from.direction && (1..4).contains(&from.steps);
+                    if to.steps > 10 || early_turn {
+                        None
+                    } else {
+                        heat(to)
+                    }
+                },
+            )
+            .unwrap();
+        assert_eq!(total, 94);
+    }
+
+    #[test]
+    fn counts_shortest_paths() {
+        let grid = Grid::new(3, 3, b'.');
+        let goal = Point::new(2, 2);
+        assert_eq!(
+            grid.count_shortest_paths(ORIGIN, goal, |_, _| Some(1)),
+            Some((4, 6))
+        );
+
+        // the wall leaves two ways around it.
+        let grid: Grid<u8> = Grid::parse("...\n.#.\n...\n");
+        let cost = |_: Point, to: Point| (grid[to] != b'#').then_some(1);
+        assert_eq!(grid.count_shortest_paths(ORIGIN, goal, cost), Some((4, 2)));
+    }
+
+    #[test]
+    fn handles_unreachable_goals() {
+        let grid: Grid<u8> = Grid::parse(MAZE);
+        let goal = Point::new(4, 4);
+        let cost = |_: Point, to: Point| (grid[to] != b'#').then_some(1);
+
+        assert_eq!(grid.bfs_path(ORIGIN, goal, open), None);
+        assert_eq!(grid.dijkstra(ORIGIN, |p| *p == goal, cost), None);
+        assert_eq!(grid.astar(ORIGIN, goal, cost), None);
+        assert_eq!(grid.count_shortest_paths(ORIGIN, goal, cost), None);
+    }
+}
@@ -0,0 +1,363 @@
+use crate::utils::grid::Grid;
+use crate::utils::point::{DIAGONAL, DOWN, LEFT, Point, RIGHT, UP};
+use hashbrown::HashMap;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A grid without fixed bounds that only stores occupied cells.
+///
+/// Bounds grow as cells are inserted, but do not shrink on removal until
+/// [`SparseGrid::shrink_bounds`] is called.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    // NOTE: private, so that every insertion goes through `grow`.
+    cells: HashMap<Point, T>,
+    min: Point,
+    max: Point,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            min: Point::new(i64::MAX, i64::MAX),
+            max: Point::new(i64::MIN, i64::MIN),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    fn grow(&mut self, p: Point) {
+        self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    #[inline]
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.grow(p);
+        self.cells.insert(p, value)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, p: &Point) -> Option<T> {
+        self.cells.remove(p)
+    }
+
+    #[inline]
+    pub fn get(&self, p: &Point) -> Option<&T> {
+        self.cells.get(p)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, p: &Point) -> Option<&mut T> {
+        self.cells.get_mut(p)
+    }
+
+    #[inline]
+    pub fn get_or_insert_with(&mut self, p: Point, f: impl FnOnce() -> T) -> &mut T {
+        self.grow(p);
+        self.cells.entry(p).or_insert_with(f)
+    }
+
+    #[inline]
+    pub fn contains(&self, p: &Point) -> bool {
+        self.cells.contains_key(p)
+    }
+
+    /// Smallest and largest corner of all cells inserted so far, `None` if the grid is empty.
+    #[inline]
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        (!self.is_empty()).then_some((self.min, self.max))
+    }
+
+    /// Recomputes the bounds from the cells that are left.
+    pub fn shrink_bounds(&mut self) {
+        let points: Vec<Point> = self.cells.keys().copied().collect();
+        self.min = Point::new(i64::MAX, i64::MAX);
+        self.max = Point::new(i64::MIN, i64::MIN);
+        points.into_iter().for_each(|p| self.grow(p));
+    }
+
+    #[inline]
+    pub fn width(&self) -> i64 {
+        self.bounds().map_or(0, |(min, max)| max.x - min.x + 1)
+    }
+
+    #[inline]
+    pub fn height(&self) -> i64 {
+        self.bounds().map_or(0, |(min, max)| max.y - min.y + 1)
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, p: &Point) -> bool {
+        p.x >= self.min.x && p.y >= self.min.y && p.x <= self.max.x && p.y <= self.max.y
+    }
+
+    /// Occupied points, in no particular order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        self.cells.keys().copied()
+    }
+
+    /// Occupied points with their cells, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, c)| (*p, c))
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.cells.iter_mut().map(|(p, c)| (*p, c))
+    }
+
+    /// Occupied orthogonal neighbors.
+    #[inline]
+    pub fn orthogonal_neighbors(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbors_by(point, [RIGHT, DOWN, LEFT, UP])
+    }
+
+    /// All 8 occupied neighbors, in reading order.
+    #[inline]
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbors_by(point, DIAGONAL)
+    }
+
+    /// Occupied neighbors at custom offsets.
+    #[inline]
+    pub fn neighbors_by<const N: usize>(
+        &self,
+        point: Point,
+        offsets: [Point; N],
+    ) -> impl Iterator<Item = Point> {
+        self.cells_by(point, offsets).map(|(p, _)| p)
+    }
+
+    #[inline]
+    pub fn orthogonal_cells(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_by(point, [RIGHT, DOWN, LEFT, UP])
+    }
+
+    #[inline]
+    pub fn neighbor_cells(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.cells_by(point, DIAGONAL)
+    }
+
+    /// Occupied neighbors at custom offsets, with their cells.
+    #[inline]
+    pub fn cells_by<const N: usize>(
+        &self,
+        point: Point,
+        offsets: [Point; N],
+    ) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .neighbors_by(offsets)
+            .into_iter()
+            .filter_map(|p| Some((p, self.get(&p)?)))
+    }
+
+    /// Displays the bounds of the grid with a character for every occupied cell and `.` for
+    /// empty ones, see [`Grid::display_with`].
+    pub fn display_with<F: Fn(&T) -> char>(&self, f: F) -> SparseGridDisplay<'_, T, F> {
+        SparseGridDisplay {
+            grid: self,
+            to_char: f,
+            empty: '.',
+            highlights: vec![],
+        }
+    }
+
+    /// Copies the cells into a grid that covers the bounds, filling the gaps with `empty`.
+    /// Returns the grid and the point that its origin corresponds to.
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, Point)
+    where
+        T: Clone,
+    {
+        let Some((min, _)) = self.bounds() else {
+            return (Grid::new(0, 0, empty), Point::new(0, 0));
+        };
+
+        let mut grid = Grid::new(self.width(), self.height(), empty);
+        for (p, c) in self.cells() {
+            grid[p - min] = c.clone();
+        }
+
+        (grid, min)
+    }
+
+    /// Copies the cells of a grid that satisfy `keep`.
+    pub fn from_grid(grid: &Grid<T>, mut keep: impl FnMut(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        grid.cells()
+            .filter(|(_, c)| keep(c))
+            .map(|(p, c)| (p, c.clone()))
+            .collect()
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (p, c) in iter {
+            self.insert(p, c);
+        }
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        self.get(&index)
+            .unwrap_or_else(|| panic!("no cell at {index}"))
+    }
+}
+
+impl<T> IndexMut<Point> for SparseGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        self.get_mut(&index)
+            .unwrap_or_else(|| panic!("no cell at {index}"))
+    }
+}
+
+impl<T: Copy + Into<char>> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(|&c| c.into()).fmt(f)
+    }
+}
+
+pub struct SparseGridDisplay<'a, T, F> {
+    grid: &'a SparseGrid<T>,
+    to_char: F,
+    empty: char,
+    highlights: Vec<(Point, char)>,
+}
+
+impl<T, F> SparseGridDisplay<'_, T, F> {
+    /// Shows empty cells as `c` instead of `.`.
+    #[must_use]
+    pub fn empty(mut self, c: char) -> Self {
+        self.empty = c;
+        self
+    }
+
+    /// Shows the given points as `c` instead of their cells. Points outside the bounds are
+    /// not shown.
+    #[must_use]
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, c: char) -> Self {
+        self.highlights.extend(points.into_iter().map(|p| (p, c)));
+        self
+    }
+}
+
+impl<T, F: Fn(&T) -> char> fmt::Display for SparseGridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.grid.bounds() else {
+            return Ok(());
+        };
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let point = Point::new(x, y);
+                // NOTE: later highlights win
+                let c = match self.highlights.iter().rev().find(|(p, _)| *p == point) {
+                    Some((_, c)) => *c,
+                    None => self.grid.get(&point).map_or(self.empty, &self.to_char),
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SparseGrid;
+    use crate::utils::grid::Grid;
+    use crate::utils::point::Point;
+
+    #[test]
+    fn grows_bounds_on_insertion() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+
+        grid.insert(Point::new(2, -1), 'a');
+        assert_eq!(grid.bounds(), Some((Point::new(2, -1), Point::new(2, -1))));
+
+        grid.insert(Point::new(-3, 4), 'b');
+        *grid.get_or_insert_with(Point::new(5, 0), || 'c') = 'd';
+        assert_eq!(grid.bounds(), Some((Point::new(-3, -1), Point::new(5, 4))));
+        assert_eq!((grid.width(), grid.height()), (9, 6));
+        assert_eq!(grid[Point::new(5, 0)], 'd');
+        assert!(grid.in_bounds(&Point::new(0, 0)));
+        assert!(!grid.in_bounds(&Point::new(6, 0)));
+    }
+
+    #[test]
+    fn shrinks_bounds_after_removal() {
+        let mut grid: SparseGrid<char> = [(Point::new(0, 0), 'a'), (Point::new(4, 3), 'b')]
+            .into_iter()
+            .collect();
+
+        grid.remove(&Point::new(4, 3));
+        assert_eq!(grid.bounds(), Some((Point::new(0, 0), Point::new(4, 3))));
+
+        grid.shrink_bounds();
+        assert_eq!(grid.bounds(), Some((Point::new(0, 0), Point::new(0, 0))));
+
+        grid.remove(&Point::new(0, 0));
+        grid.shrink_bounds();
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn round_trips_grids() {
+        let grid: Grid<u8> = Grid::parse("#..\n.#.\n..#\n");
+        let sparse = SparseGrid::from_grid(&grid, |c| *c == b'#');
+        assert_eq!(sparse.len(), 3);
+        assert_eq!(sparse.to_string(), "#..\n.#.\n..#\n");
+
+        let (dense, origin) = sparse.to_grid(b'.');
+        assert_eq!(origin, Point::new(0, 0));
+        assert_eq!(dense, grid);
+    }
+
+    #[test]
+    fn converts_offset_bounds() {
+        let sparse: SparseGrid<u8> = [(Point::new(-2, 5), b'a'), (Point::new(-1, 6), b'b')]
+            .into_iter()
+            .collect();
+        let (dense, origin) = sparse.to_grid(b' ');
+        assert_eq!(origin, Point::new(-2, 5));
+        assert_eq!(dense.to_string(), "a \n b\n");
+
+        let empty: SparseGrid<u8> = SparseGrid::new();
+        let (dense, _) = empty.to_grid(b' ');
+        assert_eq!((dense.width, dense.height), (0, 0));
+    }
+}